@@ -0,0 +1,327 @@
+//! Fetching puzzle inputs and submitting answers, either to the puzzle site
+//! or to the local stand-in in `mock`
+
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH}
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{html, runner};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Year of the puzzles in this workspace
+pub const YEAR: u32 = 2023;
+
+/// Session file in the workspace root, used when `AOC_SESSION` isn't set
+pub const SESSION_FILE: &str = ".aoc-session";
+
+/// Shortest time between requests, shared by every run of the tool
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Response to a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint which way
+    Wrong
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong")
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    Io(io::Error),
+    Transport(String),
+    Status {
+        code: u16,
+        body: String
+    },
+    /// Answer wasn't checked, with the site's message saying how long to wait
+    TooSoon(String),
+    /// Part was already solved, or part 1 hasn't been solved yet
+    WrongLevel,
+    /// Answer page didn't have any of the known messages
+    UnknownResponse(String)
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NoSession => {
+                write!(f, "Session cookie should be in AOC_SESSION or {SESSION_FILE}")
+            },
+            ClientError::Io(err) => write!(f, "{err}"),
+            ClientError::Transport(err) => write!(f, "Request failed: {err}"),
+            ClientError::Status { code, body } => write!(f, "Server returned {code}: {}", body.trim()),
+            ClientError::TooSoon(message) => write!(f, "{message}"),
+            ClientError::WrongLevel => write!(f, "Part is already solved or not unlocked yet"),
+            ClientError::UnknownResponse(body) => write!(f, "Unexpected answer page:\n{body}")
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(code, response) => ClientError::Status {
+                code,
+                body: response.into_string().unwrap_or_default()
+            },
+            ureq::Error::Transport(err) => ClientError::Transport(err.to_string())
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    year: u32,
+    session: String,
+    agent: ureq::Agent,
+    /// Holds the time of the last request, so separate runs share the limit
+    stamp_file: PathBuf,
+    min_interval: Duration
+}
+
+impl Client {
+    pub fn new(base_url: &str, year: u32, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!("aoc-runner/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Client {
+            base_url: base_url.trim_end_matches('/').into(),
+            year,
+            session: session.into(),
+            agent,
+            stamp_file: runner::workspace_root().join("target/aoc-last-request"),
+            min_interval: MIN_INTERVAL
+        }
+    }
+
+    /// Client for `AOC_URL`, or the puzzle site if it isn't set, with the
+    /// session from `AOC_SESSION` or the session file
+    pub fn from_env() -> Result<Self, ClientError> {
+        let base_url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.into());
+
+        let session = env::var("AOC_SESSION")
+            .or_else(|_| fs::read_to_string(runner::workspace_root().join(SESSION_FILE)))
+            .map(|session| session.trim().to_string())
+            .unwrap_or_default();
+
+        if session.is_empty() {
+            return Err(ClientError::NoSession);
+        }
+
+        Ok(Client::new(&base_url, YEAR, &session))
+    }
+
+    pub fn with_rate_limit(mut self, stamp_file: &Path, min_interval: Duration) -> Self {
+        self.stamp_file = stamp_file.into();
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Sleep until the interval since the last request has passed
+    fn wait_turn(&self) -> Result<(), ClientError> {
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        let last = fs::read_to_string(&self.stamp_file).ok()
+            .and_then(|stamp| stamp.trim().parse::<u64>().ok())
+            .map(Duration::from_nanos);
+
+        if let Some(wait) = last.and_then(|last| (last + self.min_interval).checked_sub(now())) {
+            thread::sleep(wait);
+        }
+
+        if let Some(dir) = self.stamp_file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.stamp_file, now().as_nanos().to_string())?;
+
+        Ok(())
+    }
+
+    fn day_url(&self, day: u32) -> String {
+        format!("{}/{}/day/{day}", self.base_url, self.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, day: u32) -> Result<String, ClientError> {
+        self.wait_turn()?;
+
+        let response = self.agent.get(&format!("{}/input", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Input file for the day, downloaded only if it isn't already in `dir`.
+    /// Returns the path and whether it was downloaded.
+    pub fn cached_input(&self, dir: &Path, day: u32) -> Result<(PathBuf, bool), ClientError> {
+        let path = dir.join("input.txt");
+        if path.exists() {
+            return Ok((path, false));
+        }
+
+        let input = self.input(day)?;
+
+        fs::create_dir_all(dir)?;
+        fs::write(&path, input)?;
+
+        Ok((path, true))
+    }
+
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Verdict, ClientError> {
+        self.wait_turn()?;
+
+        let response = self.agent.post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        parse_verdict(&response.into_string()?)
+    }
+}
+
+/// Verdict from the text of the answer page
+pub fn parse_verdict(page: &str) -> Result<Verdict, ClientError> {
+    // the message is in the article, the rest of the page has links that
+    // could match by accident
+    let message = page.split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(page);
+
+    if message.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if message.contains("your answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if message.contains("your answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if message.contains("That's not the right answer") {
+        Ok(Verdict::Wrong)
+    } else if message.contains("You gave an answer too recently") {
+        Err(ClientError::TooSoon(html::text(message).trim().into()))
+    } else if message.contains("You don't seem to be solving the right level") {
+        Err(ClientError::WrongLevel)
+    } else {
+        Err(ClientError::UnknownResponse(html::text(message).trim().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Instant};
+
+    use super::*;
+    use crate::{generate::Generated, mock::MockServer};
+
+    fn server() -> MockServer {
+        let puzzle = Generated {
+            input: "LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n".into(),
+            part1: Some("1".into()),
+            part2: Some("70".into())
+        };
+
+        MockServer::start("127.0.0.1:0", "abc", HashMap::from([(8, puzzle)])).unwrap()
+    }
+
+    fn client(server: &MockServer, session: &str, dir: &Path) -> Client {
+        Client::new(server.url(), YEAR, session)
+            .with_rate_limit(&dir.join("stamp"), Duration::ZERO)
+    }
+
+    #[test]
+    fn fetch_cached_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = server();
+        let client = client(&server, "abc", dir.path());
+
+        let (path, fetched) = client.cached_input(&dir.path().join("day8"), 8).unwrap();
+        assert!(fetched);
+        assert!(fs::read_to_string(&path).unwrap().starts_with("LR\n"));
+
+        assert_eq!(client.cached_input(&dir.path().join("day8"), 8).unwrap(), (path, false));
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = server();
+
+        let err = client(&server, "wrong", dir.path()).input(8).err();
+        assert!(matches!(err, Some(ClientError::Status { code: 400, .. })), "{err:?}");
+
+        let err = client(&server, "abc", dir.path()).input(9).err();
+        assert!(matches!(err, Some(ClientError::Status { code: 404, .. })), "{err:?}");
+    }
+
+    #[test]
+    fn submit_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = server();
+        let client = client(&server, "abc", dir.path());
+
+        assert!(matches!(client.submit(8, 2, "70"), Err(ClientError::WrongLevel)));
+        assert_eq!(client.submit(8, 1, "0").unwrap(), Verdict::TooLow);
+        assert_eq!(client.submit(8, 1, "1").unwrap(), Verdict::Correct);
+        assert!(matches!(client.submit(8, 1, "1"), Err(ClientError::WrongLevel)));
+        assert_eq!(client.submit(8, 2, "100").unwrap(), Verdict::TooHigh);
+        assert_eq!(client.submit(8, 2, "seventy").unwrap(), Verdict::Wrong);
+        assert_eq!(client.submit(8, 2, "70").unwrap(), Verdict::Correct);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = server();
+        let client = client(&server, "abc", dir.path())
+            .with_rate_limit(&dir.path().join("stamp"), Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.input(8).unwrap();
+        client.input(8).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn too_soon() {
+        let page = "<main><article><p>You gave an answer too recently; you have to wait \
+                    after submitting an answer before trying again.  You have 41s left to \
+                    wait. <a href=\"/2023/day/8\">[Return to Day 8]</a></p></article></main>";
+
+        match parse_verdict(page) {
+            Err(ClientError::TooSoon(message)) => assert!(message.ends_with("41s left to wait. [Return to Day 8]")),
+            other => panic!("{other:?}")
+        }
+    }
+}
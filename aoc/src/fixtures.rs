@@ -0,0 +1,61 @@
+//! The `tests.json` index of a day's data directory, holding the answers for
+//! each input file in the format read by the `test.sh` scripts
+
+use std::{fs, io, path::Path};
+
+use serde_json::{Map, Value};
+
+pub const INDEX_FILE: &str = "tests.json";
+
+/// Add answers for an input file, keeping any other answers already in the
+/// index. Parts are 1 or 2, written as `part1` and `part2`.
+pub fn add_answers(dir: &Path, file_name: &str, answers: &[(u32, &str)]) -> io::Result<()> {
+    let index = dir.join(INDEX_FILE);
+
+    let mut tests: Map<String, Value> = match fs::read_to_string(&index) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {err}", index.display())))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Map::new(),
+        Err(err) => return Err(err)
+    };
+
+    let entry = tests.entry(file_name)
+        .or_insert_with(|| Map::new().into());
+
+    let Some(entry) = entry.as_object_mut() else {
+        let message = format!("{}: answers for {file_name} should be an object", index.display());
+        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+    };
+
+    for (part, answer) in answers {
+        entry.insert(format!("part{part}"), (*answer).into());
+    }
+
+    // keep part1 before part2 whichever was added first
+    entry.sort_keys();
+
+    fs::create_dir_all(dir)?;
+    let json = serde_json::to_string_pretty(&tests).expect("tests should serialize to json");
+    fs::write(index, json + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        add_answers(dir, "sample.txt", &[(1, "142")]).unwrap();
+        add_answers(dir, "input.txt", &[(2, "70")]).unwrap();
+        add_answers(dir, "input.txt", &[(1, "6")]).unwrap();
+
+        let json: Value = serde_json::from_str(&fs::read_to_string(dir.join(INDEX_FILE)).unwrap()).unwrap();
+        assert_eq!(
+            json.to_string(),
+            r#"{"sample.txt":{"part1":"142"},"input.txt":{"part1":"6","part2":"70"}}"#
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Fields of pipes with one closed loop through the start
+//!
+//! The loop is the outline of a random region of cells, traced through the
+//! corners of the cells so each corner on the outline becomes a pipe tile.
+//! The region is grown without any two cells touching only at a corner and
+//! has its holes filled, so the outline is one simple loop. The farthest
+//! tile is half the loop length, and Pick's theorem gives the tiles inside
+//! from the region's area: `inside = area - loop / 2 + 1`.
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const JUNK: &[u8] = b"...|-LJ7F";
+
+struct Region {
+    cols: usize,
+    rows: usize,
+    cells: Vec<bool>
+}
+
+impl Region {
+    fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.cols && (y as usize) < self.rows
+            && self.cells[y as usize * self.cols + x as usize]
+    }
+
+    /// Whether any 2x2 square holding the cell has only a diagonal pair set
+    fn pinched_at(&self, x: i64, y: i64) -> bool {
+        [(-1, -1), (0, -1), (-1, 0), (0, 0)].iter().any(|(dx, dy)| {
+            let (x, y) = (x + dx, y + dy);
+            let (a, b) = (self.contains(x, y), self.contains(x + 1, y));
+            let (c, d) = (self.contains(x, y + 1), self.contains(x + 1, y + 1));
+            a == d && b == c && a != b
+        })
+    }
+
+    fn grow<R: Rng>(&mut self, rng: &mut R) {
+        let start = (rng.gen_range(0..self.cols), rng.gen_range(0..self.rows));
+        self.cells[start.1 * self.cols + start.0] = true;
+
+        let mut members = vec![start];
+        let target = self.cols * self.rows / 2;
+
+        for _ in 0..self.cols * self.rows * 20 {
+            if members.len() >= target {
+                break;
+            }
+
+            let (x, y) = *members.choose(rng).unwrap();
+            let (dx, dy) = *[(0, -1), (1, 0), (0, 1), (-1, 0)].choose(rng).unwrap();
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+
+            if nx < 0 || ny < 0 || nx as usize >= self.cols || ny as usize >= self.rows
+                || self.contains(nx, ny) {
+                continue;
+            }
+
+            let index = ny as usize * self.cols + nx as usize;
+            self.cells[index] = true;
+            if self.pinched_at(nx, ny) {
+                self.cells[index] = false;
+            } else {
+                members.push((nx as usize, ny as usize));
+            }
+        }
+    }
+
+    /// Add every cell that can't be reached from outside the region
+    fn fill_holes(&mut self) {
+        // flood from a border one cell wider than the region
+        let (cols, rows) = (self.cols as i64 + 2, self.rows as i64 + 2);
+        let mut outside = vec![false; (cols * rows) as usize];
+        let mut stack = vec![(-1i64, -1i64)];
+
+        while let Some((x, y)) = stack.pop() {
+            let index = ((y + 1) * cols + x + 1) as usize;
+            if outside[index] || self.contains(x, y) {
+                continue;
+            }
+            outside[index] = true;
+
+            for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx >= -1 && ny >= -1 && nx < cols - 1 && ny < rows - 1 {
+                    stack.push((nx, ny));
+                }
+            }
+        }
+
+        for y in 0..self.rows {
+            for x in 0..self.cols {
+                if !outside[(y + 1) * cols as usize + x + 1] {
+                    self.cells[y * self.cols + x] = true;
+                }
+            }
+        }
+    }
+}
+
+/// `size` is the width and height of the region the loop is drawn around
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let side = size.max(2);
+    let mut region = Region { cols: side, rows: side, cells: vec![false; side * side] };
+    region.grow(rng);
+    region.fill_holes();
+
+    // tile (x + 1, y + 1) is the top left corner of cell (x, y), leaving a
+    // margin of one tile around the loop
+    let width = side + 3;
+    let mut tiles: Vec<u8> = (0..width * width)
+        .map(|_| *JUNK.choose(rng).unwrap())
+        .collect();
+
+    // directions of the outline edges at each corner, N, E, S and W from the
+    // highest bit
+    let mut edges = vec![0u8; width * width];
+    let corner = |x: usize, y: usize| (y + 1) * width + x + 1;
+
+    for y in 0..side {
+        for x in 0..side {
+            let (cx, cy) = (x as i64, y as i64);
+            if !region.contains(cx, cy) {
+                continue;
+            }
+            if !region.contains(cx, cy - 1) {
+                edges[corner(x, y)] |= 0b0100;
+                edges[corner(x + 1, y)] |= 0b0001;
+            }
+            if !region.contains(cx, cy + 1) {
+                edges[corner(x, y + 1)] |= 0b0100;
+                edges[corner(x + 1, y + 1)] |= 0b0001;
+            }
+            if !region.contains(cx - 1, cy) {
+                edges[corner(x, y)] |= 0b0010;
+                edges[corner(x, y + 1)] |= 0b1000;
+            }
+            if !region.contains(cx + 1, cy) {
+                edges[corner(x + 1, y)] |= 0b0010;
+                edges[corner(x + 1, y + 1)] |= 0b1000;
+            }
+        }
+    }
+
+    let mut loop_tiles = vec![];
+    for (i, directions) in edges.iter().enumerate() {
+        let pipe = match directions {
+            0 => continue,
+            0b1010 => b'|',
+            0b0101 => b'-',
+            0b1100 => b'L',
+            0b1001 => b'J',
+            0b0011 => b'7',
+            0b0110 => b'F',
+            _ => unreachable!("outline should have two edges at each corner")
+        };
+        tiles[i] = pipe;
+        loop_tiles.push(i);
+    }
+
+    // clear junk next to the start so only the loop connects to it
+    let start = *loop_tiles.choose(rng).unwrap();
+    tiles[start] = b'S';
+    for neighbour in [start - width, start + 1, start + width, start - 1] {
+        if edges[neighbour] == 0 {
+            tiles[neighbour] = b'.';
+        }
+    }
+
+    let input = tiles.chunks(width)
+        .map(|row| String::from_utf8_lossy(row).into_owned() + "\n")
+        .collect();
+
+    let area = region.cells.iter().filter(|c| **c).count();
+    let inside = area + 1 - loop_tiles.len() / 2;
+
+    Generated::new(input, loop_tiles.len() / 2, inside)
+}
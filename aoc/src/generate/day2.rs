@@ -0,0 +1,53 @@
+//! Game logs of cubes drawn from a bag
+
+use std::fmt::Write;
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// Bag the part 1 answer is checked against, same as the day2 default
+const BAG: [u32; 3] = [12, 13, 14];
+
+/// `size` is the number of games
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let mut input = String::new();
+    let mut possible_total = 0;
+    let mut power_total = 0;
+
+    for number in 1..=size {
+        let mut max = [0; 3];
+        let mut draws = vec![];
+
+        for _ in 0..rng.gen_range(1..=6) {
+            let mut colours: Vec<usize> = (0..COLOURS.len())
+                .filter(|_| rng.gen_bool(0.6))
+                .collect();
+            if colours.is_empty() {
+                colours.push(rng.gen_range(0..COLOURS.len()));
+            }
+            colours.shuffle(rng);
+
+            let mut cubes = vec![];
+            for colour in colours {
+                // a few over the bag limit so some games are impossible
+                let count = rng.gen_range(1..=BAG[colour] + 3);
+                max[colour] = max[colour].max(count);
+                cubes.push(format!("{count} {}", COLOURS[colour]));
+            }
+
+            draws.push(cubes.join(", "));
+        }
+
+        writeln!(input, "Game {number}: {}", draws.join("; ")).unwrap();
+
+        if max.iter().zip(BAG).all(|(max, limit)| *max <= limit) {
+            possible_total += number;
+        }
+        power_total += max.iter().product::<u32>();
+    }
+
+    Generated::new(input, possible_total, power_total)
+}
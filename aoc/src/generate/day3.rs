@@ -0,0 +1,130 @@
+//! Engine schematics made of small blocks of numbers and symbols
+//!
+//! Each block is 4 rows by 10 columns with the last row and column left
+//! empty, so nothing in one block is adjacent to anything in another and the
+//! parts and gears of each block are known when it is placed.
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::Generated;
+
+const BLOCK_ROWS: usize = 4;
+const BLOCK_COLS: usize = 10;
+
+const SYMBOLS: &[u8] = b"#$%&*+-/=@";
+
+struct Schematic {
+    cells: Vec<Vec<char>>,
+    part_total: u32,
+    gear_total: u32
+}
+
+impl Schematic {
+    fn put(&mut self, row: usize, col: usize, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            self.cells[row][col + i] = c;
+        }
+    }
+}
+
+/// `size` is the number of blocks
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let blocks_across = (size as f64).sqrt().ceil() as usize;
+    let blocks_down = size.div_ceil(blocks_across);
+
+    let mut schematic = Schematic {
+        cells: vec![vec!['.'; blocks_across * BLOCK_COLS]; blocks_down * BLOCK_ROWS],
+        part_total: 0,
+        gear_total: 0
+    };
+
+    for block in 0..size {
+        let row = (block / blocks_across) * BLOCK_ROWS;
+        let col = (block % blocks_across) * BLOCK_COLS;
+        add_block(rng, &mut schematic, row, col);
+    }
+
+    let input = schematic.cells.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect();
+
+    Generated::new(input, schematic.part_total, schematic.gear_total)
+}
+
+/// Random number of 1 to 3 digits without a leading zero
+fn number<R: Rng>(rng: &mut R) -> (u32, String) {
+    let digits = rng.gen_range(1..=3);
+    let value = rng.gen_range(10u32.pow(digits - 1).max(1)..10u32.pow(digits));
+    (value, value.to_string())
+}
+
+fn symbol<R: Rng>(rng: &mut R) -> char {
+    *SYMBOLS.choose(rng).unwrap() as char
+}
+
+fn add_block<R: Rng>(rng: &mut R, schematic: &mut Schematic, row: usize, col: usize) {
+    match rng.gen_range(0..6) {
+        // number with no symbol
+        0 => {
+            let (_, text) = number(rng);
+            schematic.put(row + 1, col + rng.gen_range(1..=3), &text);
+        },
+        // number with one symbol somewhere around it
+        1 => {
+            let (value, text) = number(rng);
+            let start = col + rng.gen_range(1..=3);
+            let end = start + text.len();
+            schematic.put(row + 1, start, &text);
+
+            let mut around = vec![(row + 1, start - 1), (row + 1, end)];
+            for c in (start - 1)..=end {
+                around.push((row, c));
+                around.push((row + 2, c));
+            }
+            let (r, c) = *around.choose(rng).unwrap();
+            schematic.cells[r][c] = symbol(rng);
+
+            schematic.part_total += value;
+        },
+        // gear, with one number above and one below
+        2 => {
+            let (a, a_text) = number(rng);
+            let (b, b_text) = number(rng);
+            let a_end = col + rng.gen_range(3..=5);
+            schematic.put(row, a_end + 1 - a_text.len(), &a_text);
+            schematic.cells[row + 1][col + 4] = '*';
+            schematic.put(row + 2, col + rng.gen_range(3..=5), &b_text);
+
+            schematic.part_total += a + b;
+            schematic.gear_total += a * b;
+        },
+        // `*` next to three numbers is not a gear
+        3 => {
+            let (a, a_text) = number(rng);
+            let (b, b_text) = number(rng);
+            let (c, c_text) = number(rng);
+            schematic.put(row, col + 5 - a_text.len(), &a_text);
+            schematic.cells[row + 1][col + 4] = '*';
+            schematic.put(row + 1, col + 5, &c_text);
+            schematic.put(row + 2, col + 4, &b_text);
+
+            schematic.part_total += a + b + c;
+        },
+        // two numbers sharing the symbol between them
+        4 => {
+            let (a, a_text) = number(rng);
+            let (b, b_text) = number(rng);
+            let symbol = symbol(rng);
+            schematic.put(row + 1, col + 1, &format!("{a_text}{symbol}{b_text}"));
+
+            schematic.part_total += a + b;
+            if symbol == '*' {
+                schematic.gear_total += a * b;
+            }
+        },
+        // symbol on its own
+        _ => {
+            schematic.cells[row + 1][col + rng.gen_range(1..=8)] = symbol(rng);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Almanacs of seeds and the maps from one category to the next
+
+use std::{fmt::Write, ops::Range};
+
+use rand::{seq::index, Rng};
+
+use super::Generated;
+
+const CATEGORIES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location"
+];
+
+struct Map {
+    src: Range<u64>,
+    dst_start: u64
+}
+
+/// `size` scales the range of values, the number of maps in each category
+/// and the total length of the part 2 seed ranges
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let limit = size as u64 * 1000;
+    let pairs = (2 + size / 10).min(10);
+
+    let seeds: Vec<(u64, u64)> = (0..pairs)
+        .map(|_| (rng.gen_range(0..limit), rng.gen_range(1..=size as u64 * 10)))
+        .collect();
+
+    let categories: Vec<Vec<Map>> = CATEGORIES.iter()
+        .map(|_| category_maps(rng, limit, size.clamp(2, 40)))
+        .collect();
+
+    let mut input = String::from("seeds:");
+    for (start, len) in &seeds {
+        write!(input, " {start} {len}").unwrap();
+    }
+    input.push('\n');
+
+    for (name, maps) in CATEGORIES.iter().zip(&categories) {
+        write!(input, "\n{name} map:\n").unwrap();
+        for map in maps {
+            let len = map.src.end - map.src.start;
+            writeln!(input, "{} {} {len}", map.dst_start, map.src.start).unwrap();
+        }
+    }
+
+    let location = |seed: u64| {
+        categories.iter().fold(seed, |val, maps| {
+            maps.iter()
+                .find(|map| map.src.contains(&val))
+                .map_or(val, |map| map.dst_start + (val - map.src.start))
+        })
+    };
+
+    let lowest_single = seeds.iter()
+        .flat_map(|(start, len)| [*start, *len])
+        .map(location)
+        .min()
+        .unwrap();
+
+    let lowest_range = seeds.iter()
+        .flat_map(|(start, len)| *start..(start + len))
+        .map(location)
+        .min()
+        .unwrap();
+
+    Generated::new(input, lowest_single, lowest_range)
+}
+
+/// Maps with source ranges that don't overlap, so the answer doesn't depend
+/// on which map is checked first
+fn category_maps<R: Rng>(rng: &mut R, limit: u64, count: usize) -> Vec<Map> {
+    let mut bounds: Vec<u64> = index::sample(rng, limit as usize, count * 2)
+        .into_iter()
+        .map(|i| i as u64)
+        .collect();
+    bounds.sort();
+
+    bounds.chunks(2)
+        .map(|pair| Map {
+            src: pair[0]..pair[1],
+            dst_start: rng.gen_range(0..limit)
+        })
+        .collect()
+}
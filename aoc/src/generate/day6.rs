@@ -0,0 +1,78 @@
+//! Sheets of race times and record distances
+
+use rand::Rng;
+
+use super::Generated;
+
+/// `size` is the number of races, up to 4 so the part 2 race stays small
+/// enough to solve by trying every button time
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let races: Vec<(u64, u64)> = (0..size.min(4))
+        .map(|_| {
+            let time = rng.gen_range(7..100);
+            let best = (time / 2) * (time - time / 2);
+            (time, rng.gen_range(best / 2..best))
+        })
+        .collect();
+
+    let times: Vec<String> = races.iter().map(|(t, _)| t.to_string()).collect();
+    let distances: Vec<String> = races.iter().map(|(_, d)| d.to_string()).collect();
+
+    let input = format!(
+        "Time:     {}\nDistance: {}\n",
+        pad_join(&times, &distances),
+        pad_join(&distances, &times)
+    );
+
+    let product: u64 = races.iter()
+        .map(|(time, distance)| winning_count(*time, *distance))
+        .product();
+
+    let single_time = times.concat().parse().unwrap();
+    let single_distance = distances.concat().parse().unwrap();
+
+    Generated::new(input, product, winning_count(single_time, single_distance))
+}
+
+/// Join values into columns wide enough for the values on the other line
+fn pad_join(values: &[String], other: &[String]) -> String {
+    values.iter()
+        .zip(other)
+        .map(|(value, other)| format!("{value:>width$}", width = value.len().max(other.len())))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Number of button times that beat the record, found by searching for the
+/// shortest winning time since distance rises until half the race time
+fn winning_count(time: u64, record: u64) -> u64 {
+    let distance = |button: u64| button as u128 * (time - button) as u128;
+
+    let (mut low, mut high) = (1, time / 2 + 1);
+    while low < high {
+        let mid = (low + high) / 2;
+        if distance(mid) > record as u128 {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    if low > time / 2 {
+        0
+    } else {
+        time - 2 * low + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winning_count_edges() {
+        assert_eq!(winning_count(7, 9), 4);
+        assert_eq!(winning_count(8, 15), 1);
+        assert_eq!(winning_count(8, 16), 0);
+    }
+}
@@ -0,0 +1,102 @@
+//! Camel card hands with bids
+
+use std::{collections::HashSet, fmt::Write};
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::Generated;
+
+/// Cards weakest to strongest
+const CARDS: &[u8] = b"23456789TJQKA";
+
+/// `size` is the number of hands, all hands are different
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let mut seen = HashSet::new();
+    let mut hands = vec![];
+
+    while hands.len() < size {
+        // fewer kinds of card makes the stronger hand types more likely
+        let kind_count = rng.gen_range(1..=5);
+        let kinds: Vec<u8> = CARDS.choose_multiple(rng, kind_count)
+            .copied()
+            .collect();
+        let cards: Vec<u8> = (0..5)
+            .map(|_| *kinds.choose(rng).unwrap())
+            .collect();
+
+        if seen.insert(cards.clone()) {
+            hands.push((cards, rng.gen_range(1..=1000)));
+        }
+    }
+
+    let mut input = String::new();
+    for (cards, bid) in &hands {
+        writeln!(input, "{} {bid}", String::from_utf8_lossy(cards)).unwrap();
+    }
+
+    Generated::new(input, winnings(&hands, false), winnings(&hands, true))
+}
+
+fn winnings(hands: &[(Vec<u8>, u64)], wildcard: bool) -> u64 {
+    let mut ranked: Vec<_> = hands.iter()
+        .map(|(cards, bid)| {
+            let values: Vec<usize> = cards.iter()
+                .map(|c| match c {
+                    b'J' if wildcard => 0,
+                    c => CARDS.iter().position(|x| x == c).unwrap() + 1
+                })
+                .collect();
+            ((hand_strength(cards, wildcard), values), *bid)
+        })
+        .collect();
+
+    ranked.sort();
+
+    ranked.iter()
+        .enumerate()
+        .map(|(i, (_, bid))| (i as u64 + 1) * bid)
+        .sum()
+}
+
+/// Hand type from weakest (0, high card) to strongest (6, five of a kind),
+/// any jokers join the largest group of other cards
+fn hand_strength(cards: &[u8], wildcard: bool) -> u8 {
+    let mut counts: Vec<usize> = CARDS.iter()
+        .filter(|card| !(wildcard && **card == b'J'))
+        .map(|card| cards.iter().filter(|c| *c == card).count())
+        .filter(|count| *count > 0)
+        .collect();
+    counts.sort_by(|a, b| b.cmp(a));
+
+    if wildcard {
+        let jokers = cards.iter().filter(|c| **c == b'J').count();
+        match counts.first_mut() {
+            Some(most) => *most += jokers,
+            None => counts.push(jokers)
+        }
+    }
+
+    match counts.as_slice() {
+        [5] => 6,
+        [4, ..] => 5,
+        [3, 2] => 4,
+        [3, ..] => 3,
+        [2, 2, ..] => 2,
+        [2, ..] => 1,
+        _ => 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strength() {
+        assert_eq!(hand_strength(b"32T3K", false), 1);
+        assert_eq!(hand_strength(b"KTJJT", false), 2);
+        assert_eq!(hand_strength(b"KTJJT", true), 5);
+        assert_eq!(hand_strength(b"JJJJJ", true), 6);
+        assert_eq!(hand_strength(b"2345J", true), 1);
+    }
+}
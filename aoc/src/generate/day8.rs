@@ -0,0 +1,109 @@
+//! Networks of nodes with left and right instructions
+//!
+//! The path from `AAA` to `ZZZ` is a chain of nodes that only follows the
+//! instructions, with the other side of each node pointing anywhere in the
+//! chain. Its length is a multiple of the instruction count and `ZZZ` leads
+//! back to where `AAA` does, so it repeats exactly. Each other `..A` start
+//! leads into a loop through its `..Z` end, with both sides of every node
+//! the same, so it reaches the end after the loop length and repeats with
+//! that period. Part 2 is then the lowest common multiple of the lengths.
+
+use std::{collections::HashSet, fmt::Write};
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::Generated;
+
+struct Labels {
+    used: HashSet<String>
+}
+
+impl Labels {
+    /// Unique random label with the last char, or any char except `A` and `Z`
+    fn next<R: Rng>(&mut self, rng: &mut R, last: Option<char>) -> String {
+        loop {
+            let mut label: String = (0..2)
+                .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+                .collect();
+            label.push(last.unwrap_or_else(|| rng.gen_range(b'B'..=b'Y') as char));
+
+            if self.used.insert(label.clone()) {
+                return label;
+            }
+        }
+    }
+}
+
+/// `size` scales the number of paths, their lengths and the number of
+/// unreachable nodes
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let mut labels = Labels {
+        used: HashSet::from(["AAA".into(), "ZZZ".into()])
+    };
+    let mut nodes: Vec<(String, String, String)> = vec![];
+
+    let instructions: Vec<bool> = (0..rng.gen_range(2..=size.min(20) + 2))
+        .map(|_| rng.gen_bool(0.5))
+        .collect();
+
+    // path from AAA to ZZZ
+    let length = instructions.len() * rng.gen_range(1..=size.min(20));
+    let mut chain = vec!["AAA".to_string()];
+    chain.extend((1..length).map(|_| labels.next(rng, None)));
+    chain.push("ZZZ".into());
+
+    for (i, label) in chain.iter().take(length).enumerate() {
+        let next = chain[i + 1].clone();
+        let other = chain[..length].choose(rng).unwrap().clone();
+
+        if instructions[i % instructions.len()] {
+            nodes.push((label.clone(), other, next));
+        } else {
+            nodes.push((label.clone(), next, other));
+        }
+    }
+    let (_, left, right) = nodes[0].clone();
+    nodes.push(("ZZZ".into(), left, right));
+
+    // ghost paths
+    let mut lcm = length as u64;
+    for _ in 0..size.clamp(1, 5) {
+        let period = rng.gen_range(2..=(size * 5 + 2).min(400));
+        lcm = lcm / gcd(lcm, period as u64) * period as u64;
+
+        let start = labels.next(rng, Some('A'));
+        let end = labels.next(rng, Some('Z'));
+        let mut cycle: Vec<String> = (1..period).map(|_| labels.next(rng, None)).collect();
+        cycle.push(end);
+
+        nodes.push((start, cycle[0].clone(), cycle[0].clone()));
+        for (i, label) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()].clone();
+            nodes.push((label.clone(), next.clone(), next));
+        }
+    }
+
+    // nodes that can't be reached from any start
+    for _ in 0..size * 2 {
+        let label = labels.next(rng, None);
+        let left = nodes.choose(rng).unwrap().0.clone();
+        let right = nodes.choose(rng).unwrap().0.clone();
+        nodes.push((label, left, right));
+    }
+
+    nodes.shuffle(rng);
+
+    let mut input: String = instructions.iter()
+        .map(|right| if *right { 'R' } else { 'L' })
+        .collect();
+    input.push_str("\n\n");
+    for (label, left, right) in &nodes {
+        writeln!(input, "{label} = ({left}, {right})").unwrap();
+    }
+
+    Generated::new(input, length, lcm)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
@@ -0,0 +1,62 @@
+//! Histories of values that follow a polynomial
+//!
+//! Each history is `sum(c[k] * C(x, k))` for small integer `c`, which is the
+//! Newton forward difference form, so every value is an integer and the next
+//! and previous values are found by evaluating at `x = len` and `x = -1`.
+
+use std::fmt::Write;
+
+use rand::Rng;
+
+use super::Generated;
+
+/// `size` is the number of histories
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> Generated {
+    let mut input = String::new();
+    let mut next_total = 0;
+    let mut previous_total = 0;
+
+    for _ in 0..size {
+        let len = rng.gen_range(6..=21);
+        // at least one row of zero differences is needed under the last row
+        let degree = rng.gen_range(0..=6.min(len - 2));
+
+        let mut coefficients: Vec<i64> = (0..=degree)
+            .map(|_| rng.gen_range(-9..=9))
+            .collect();
+        coefficients[0] = rng.gen_range(-20..=20);
+
+        let value = |x: i64| -> i64 {
+            coefficients.iter()
+                .enumerate()
+                .map(|(k, c)| c * choose(x, k))
+                .sum()
+        };
+
+        let history: Vec<String> = (0..len as i64).map(|x| value(x).to_string()).collect();
+        writeln!(input, "{}", history.join(" ")).unwrap();
+
+        next_total += value(len as i64);
+        previous_total += value(-1);
+    }
+
+    Generated::new(input, next_total, previous_total)
+}
+
+/// Binomial coefficient `x(x-1)...(x-k+1) / k!`, which is defined for negative x
+fn choose(x: i64, k: usize) -> i64 {
+    (0..k as i64).fold(1, |acc, i| acc * (x - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binomial() {
+        assert_eq!(choose(5, 2), 10);
+        assert_eq!(choose(3, 5), 0);
+        assert_eq!(choose(-1, 3), -1);
+        assert_eq!(choose(-1, 4), 1);
+    }
+}
@@ -0,0 +1,86 @@
+//! Seeded random puzzle inputs, with the answers where they are known from
+//! how the input was built
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Days that have a generator
+pub const DAYS: [u32; 8] = [2, 3, 5, 6, 7, 8, 9, 10];
+
+/// Generated input text with its answers, `None` if an answer isn't known
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>
+}
+
+impl Generated {
+    fn new(input: String, part1: impl ToString, part2: impl ToString) -> Self {
+        Generated {
+            input,
+            part1: Some(part1.to_string()),
+            part2: Some(part2.to_string())
+        }
+    }
+}
+
+/// Generate an input for the day, the same seed and size always give the
+/// same input. Returns `None` for days without a generator.
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<Generated> {
+    // ChaCha output is stable across platforms and crate versions, unlike StdRng
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let size = size.max(1);
+
+    let generated = match day {
+        2 => day2::generate(&mut rng, size),
+        3 => day3::generate(&mut rng, size),
+        5 => day5::generate(&mut rng, size),
+        6 => day6::generate(&mut rng, size),
+        7 => day7::generate(&mut rng, size),
+        8 => day8::generate(&mut rng, size),
+        9 => day9::generate(&mut rng, size),
+        10 => day10::generate(&mut rng, size),
+        _ => return None
+    };
+
+    Some(generated)
+}
+
+/// Generated input text for each size, with a fixed seed so benchmark runs
+/// can be compared
+pub fn sized_inputs(day: u32, sizes: &[usize]) -> Vec<(usize, String)> {
+    sizes.iter()
+        .map(|size| {
+            let generated = generate(day, 1, *size).expect("day should have a generator");
+            (*size, generated.input)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in DAYS {
+            assert_eq!(generate(day, 7, 10), generate(day, 7, 10), "day{day}");
+            assert_ne!(generate(day, 7, 10), generate(day, 8, 10), "day{day}");
+        }
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert_eq!(generate(1, 0, 10), None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Timings of solver runs kept as json lines, one run per line, so runs of
+//! different revisions can be compared
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub name: String,
+    pub elapsed_ns: u64,
+    pub peak_heap: u64
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub input_hash: String,
+    /// Commit hash, with `-dirty` added if there were uncommitted changes
+    pub revision: String,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub answer: String,
+    pub phases: Vec<PhaseTiming>
+}
+
+impl Record {
+    pub fn total_ns(&self) -> u64 {
+        self.phases.iter().map(|p| p.elapsed_ns).sum()
+    }
+}
+
+pub fn append(path: &Path, record: &Record) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(record).expect("record should serialize to json");
+    writeln!(file, "{line}")
+}
+
+/// All records in the history, an empty history if the file doesn't exist yet
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err)
+    };
+
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {err}", i + 1))
+            })
+        })
+        .collect()
+}
+
+/// FNV-1a hash of the input, which stays the same across platforms and
+/// Rust versions unlike the std hasher
+pub fn hash_input(data: &[u8]) -> String {
+    let hash = data.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+const DIRTY: &str = "-dirty";
+
+/// Reasons a revision given on the command line doesn't name one recorded
+/// revision
+#[derive(Debug, PartialEq)]
+pub enum RevisionError {
+    NotFound(String),
+    Ambiguous {
+        revision: String,
+        matches: Vec<String>
+    }
+}
+
+impl fmt::Display for RevisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevisionError::NotFound(revision) => write!(f, "No runs at {revision}"),
+            RevisionError::Ambiguous { revision, matches } => {
+                write!(f, "{revision} could be any of {}", matches.join(", "))
+            }
+        }
+    }
+}
+
+/// The recorded revision named by a prefix of its commit hash. Runs with
+/// uncommitted changes are kept apart from the commit's own runs, they are
+/// only picked when the revision ends with `-dirty` too.
+pub fn resolve_revision(records: &[Record], revision: &str) -> Result<String, RevisionError> {
+    let (hash, dirty) = match revision.strip_suffix(DIRTY) {
+        Some(hash) => (hash, true),
+        None => (revision, false)
+    };
+
+    let matches: BTreeSet<&str> = records.iter()
+        .map(|record| record.revision.as_str())
+        .filter(|recorded| {
+            let (recorded_hash, recorded_dirty) = match recorded.strip_suffix(DIRTY) {
+                Some(recorded_hash) => (recorded_hash, true),
+                None => (*recorded, false)
+            };
+
+            !hash.is_empty() && recorded_hash.starts_with(hash) && recorded_dirty == dirty
+        })
+        .collect();
+
+    match matches.len() {
+        0 => Err(RevisionError::NotFound(revision.into())),
+        1 => Ok(matches.into_iter().next().unwrap().into()),
+        _ => Err(RevisionError::Ambiguous {
+            revision: revision.into(),
+            matches: matches.into_iter().map(String::from).collect()
+        })
+    }
+}
+
+/// Median total time of one solver on one input at two revisions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub base_ns: u64,
+    pub new_ns: u64
+}
+
+impl Comparison {
+    /// Relative change in time, where 0.1 is 10% slower
+    pub fn change(&self) -> f64 {
+        self.new_ns as f64 / self.base_ns.max(1) as f64 - 1.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Compare the runs of each solver and input that were timed at both
+/// revisions, which are full recorded revisions as from `resolve_revision`
+pub fn compare(records: &[Record], base: &str, new: &str) -> Vec<Comparison> {
+    type Key = (u32, u32, String);
+
+    let mut base_runs: BTreeMap<Key, (String, Vec<u64>)> = BTreeMap::new();
+    let mut new_runs: BTreeMap<Key, Vec<u64>> = BTreeMap::new();
+
+    for record in records {
+        let key = (record.day, record.part, record.input_hash.clone());
+
+        if record.revision == base {
+            base_runs.entry(key.clone())
+                .or_insert_with(|| (record.input.clone(), vec![]))
+                .1.push(record.total_ns());
+        }
+        if record.revision == new {
+            new_runs.entry(key).or_default().push(record.total_ns());
+        }
+    }
+
+    base_runs.into_iter()
+        .filter_map(|(key, (input, mut base))| {
+            let mut new = new_runs.remove(&key)?;
+            Some(Comparison {
+                day: key.0,
+                part: key.1,
+                input,
+                base_ns: median(&mut base),
+                new_ns: median(&mut new)
+            })
+        })
+        .collect()
+}
+
+fn median(values: &mut [u64]) -> u64 {
+    values.sort();
+    values[values.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u32, revision: &str, input_hash: &str, total_ns: u64) -> Record {
+        Record {
+            day,
+            part: 1,
+            input: "input.txt".into(),
+            input_hash: input_hash.into(),
+            revision: revision.into(),
+            timestamp: 0,
+            answer: "1".into(),
+            phases: vec![PhaseTiming { name: "solve".into(), elapsed_ns: total_ns, peak_heap: 0 }]
+        }
+    }
+
+    #[test]
+    fn compare_revisions() {
+        let records = vec![
+            record(8, "aaaa1111", "h1", 100),
+            record(8, "aaaa1111", "h1", 900),
+            record(8, "aaaa1111", "h1", 110),
+            record(8, "aaaa1111-dirty", "h1", 130),
+            // only at one revision
+            record(9, "aaaa1111", "h1", 100),
+            // different input
+            record(8, "aaaa1111-dirty", "h2", 50)
+        ];
+
+        let comparisons = compare(&records, "aaaa1111", "aaaa1111-dirty");
+        assert_eq!(comparisons.len(), 1);
+
+        let comparison = &comparisons[0];
+        assert_eq!((comparison.day, comparison.base_ns, comparison.new_ns), (8, 110, 130));
+        assert!(comparison.is_regression(0.1));
+        assert!(!comparison.is_regression(0.2));
+    }
+
+    #[test]
+    fn resolve_revisions() {
+        let records = vec![
+            record(8, "aaaa1111", "h1", 100),
+            record(8, "aaaa1111-dirty", "h1", 130),
+            record(8, "aaaa2222", "h1", 120)
+        ];
+
+        assert_eq!(resolve_revision(&records, "aaaa1"), Ok("aaaa1111".into()));
+        assert_eq!(resolve_revision(&records, "aaaa1-dirty"), Ok("aaaa1111-dirty".into()));
+        assert_eq!(resolve_revision(&records, "aaaa2222-dirty"), Err(RevisionError::NotFound("aaaa2222-dirty".into())));
+        assert_eq!(
+            resolve_revision(&records, "aaaa"),
+            Err(RevisionError::Ambiguous {
+                revision: "aaaa".into(),
+                matches: vec!["aaaa1111".into(), "aaaa2222".into()]
+            })
+        );
+
+        // the dirty runs stay out of the commit's own median
+        let comparisons = compare(&records, "aaaa1111", "aaaa2222");
+        assert_eq!((comparisons[0].base_ns, comparisons[0].new_ns), (100, 120));
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+
+        assert_eq!(load(&path).unwrap(), vec![]);
+
+        let records = [record(2, "aaaa", "h1", 5), record(3, "aaaa", "h1", 6)];
+        for r in &records {
+            append(&path, r).unwrap();
+        }
+        assert_eq!(load(&path).unwrap(), records);
+    }
+
+    #[test]
+    fn input_hash() {
+        assert_eq!(hash_input(b""), "cbf29ce484222325");
+        assert_eq!(hash_input(b"a"), "af63dc4c8601ec8c");
+    }
+}
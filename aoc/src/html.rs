@@ -0,0 +1,76 @@
+//! Just enough html handling for the pages of the puzzle site
+
+/// Text of the html with tags removed and entities decoded, whitespace is
+/// kept as it is
+pub fn text(html: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => ()
+        }
+    }
+
+    decode_entities(&stripped)
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';')
+            .map(|end| (&rest[1..end], end))
+            .and_then(|(name, end)| decode_entity(name).map(|c| (c, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            },
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_and_entities() {
+        assert_eq!(text("<p>a <em>&lt;b&gt;</em> &amp; &#39;c&#x27; &bogus; &</p>"), "a <b> & 'c' &bogus; &");
+        assert_eq!(text("<code>\n  x\n</code>"), "\n  x\n");
+    }
+}
@@ -0,0 +1,174 @@
+//! Answers submitted for a day with their verdicts, kept as json lines so
+//! answers already known to be wrong aren't submitted again
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Verdict;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch
+    pub timestamp: u64
+}
+
+/// Reasons an answer shouldn't be submitted
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    Solved {
+        answer: String
+    },
+    Submitted {
+        answer: String,
+        verdict: Verdict
+    },
+    /// Answer is at or above an answer that was too high
+    AboveHigh {
+        answer: String,
+        bound: String
+    },
+    /// Answer is at or below an answer that was too low
+    BelowLow {
+        answer: String,
+        bound: String
+    }
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved { answer } => write!(f, "Part is already solved, the answer was {answer}"),
+            Refusal::Submitted { answer, verdict } => {
+                write!(f, "{answer} was already submitted and was {verdict}")
+            },
+            Refusal::AboveHigh { answer, bound } => {
+                write!(f, "{answer} should be lower than {bound}, which was too high")
+            },
+            Refusal::BelowLow { answer, bound } => {
+                write!(f, "{answer} should be higher than {bound}, which was too low")
+            }
+        }
+    }
+}
+
+pub fn append(path: &Path, submission: &Submission) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(submission).expect("submission should serialize to json");
+    writeln!(file, "{line}")
+}
+
+/// All submissions in the ledger, none if the file doesn't exist yet
+pub fn load(path: &Path) -> io::Result<Vec<Submission>> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err)
+    };
+
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {err}", i + 1))
+            })
+        })
+        .collect()
+}
+
+/// Check an answer against earlier submissions for the part. Numeric answers
+/// are also checked against the closest too high and too low answers.
+pub fn check(submissions: &[Submission], part: u32, answer: &str) -> Result<(), Refusal> {
+    let submissions: Vec<_> = submissions.iter().filter(|s| s.part == part).collect();
+
+    if let Some(correct) = submissions.iter().find(|s| s.verdict == Verdict::Correct) {
+        return Err(Refusal::Solved { answer: correct.answer.clone() });
+    }
+
+    if let Some(earlier) = submissions.iter().find(|s| s.answer == answer) {
+        return Err(Refusal::Submitted { answer: answer.into(), verdict: earlier.verdict });
+    }
+
+    let Ok(value) = answer.parse::<i128>() else {
+        return Ok(());
+    };
+
+    let bound = |verdict| {
+        submissions.iter()
+            .filter(move |s| s.verdict == verdict)
+            .filter_map(|s| s.answer.parse::<i128>().ok().map(|v| (v, &s.answer)))
+    };
+
+    if let Some((_, high)) = bound(Verdict::TooHigh).filter(|(v, _)| value >= *v).min() {
+        return Err(Refusal::AboveHigh { answer: answer.into(), bound: high.clone() });
+    }
+
+    if let Some((_, low)) = bound(Verdict::TooLow).filter(|(v, _)| value <= *v).max() {
+        return Err(Refusal::BelowLow { answer: answer.into(), bound: low.clone() });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(part: u32, answer: &str, verdict: Verdict) -> Submission {
+        Submission { part, answer: answer.into(), verdict, timestamp: 0 }
+    }
+
+    #[test]
+    fn refuse_known_answers() {
+        let submissions = [
+            submission(1, "500", Verdict::TooHigh),
+            submission(1, "900", Verdict::TooHigh),
+            submission(1, "100", Verdict::TooLow),
+            submission(1, "250", Verdict::Wrong),
+            submission(2, "7", Verdict::Correct)
+        ];
+
+        assert_eq!(check(&submissions, 1, "300"), Ok(()));
+        assert_eq!(
+            check(&submissions, 1, "250"),
+            Err(Refusal::Submitted { answer: "250".into(), verdict: Verdict::Wrong })
+        );
+        assert_eq!(
+            check(&submissions, 1, "600"),
+            Err(Refusal::AboveHigh { answer: "600".into(), bound: "500".into() })
+        );
+        assert_eq!(
+            check(&submissions, 1, "-3"),
+            Err(Refusal::BelowLow { answer: "-3".into(), bound: "100".into() })
+        );
+        assert_eq!(check(&submissions, 1, "abc"), Ok(()));
+        assert_eq!(check(&submissions, 2, "8"), Err(Refusal::Solved { answer: "7".into() }));
+    }
+
+    #[test]
+    fn ledger_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.jsonl");
+
+        assert_eq!(load(&path).unwrap(), vec![]);
+
+        let submissions = [submission(1, "5", Verdict::TooLow), submission(1, "6", Verdict::Correct)];
+        for s in &submissions {
+            append(&path, s).unwrap();
+        }
+        assert_eq!(load(&path).unwrap(), submissions);
+    }
+}
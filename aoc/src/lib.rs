@@ -0,0 +1,12 @@
+//! Tooling shared by the `aoc` runner, benches and fuzz targets
+
+pub mod client;
+pub mod fixtures;
+pub mod generate;
+pub mod history;
+pub mod html;
+pub mod ledger;
+pub mod mock;
+pub mod runner;
+pub mod samples;
+pub mod scaffold;
@@ -0,0 +1,427 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH}
+};
+
+use aoc::{
+    client::{Client, Verdict},
+    fixtures,
+    generate::{self, Generated},
+    history::{self, Record},
+    ledger::{self, Submission},
+    mock::MockServer,
+    runner,
+    samples,
+    scaffold
+};
+
+/// Default history file, relative to the workspace root
+const HISTORY_FILE: &str = "bench-history.jsonl";
+
+/// Submitted answers, in each day's data directory
+const LEDGER_FILE: &str = "ledger.jsonl";
+
+fn main() {
+    let mut args = env::args();
+
+    // first arg is command name
+    let cmd_name = args.next().unwrap();
+
+    match args.next().as_deref() {
+        Some("gen") => gen(&cmd_name, args),
+        Some("run") => run(&cmd_name, args),
+        Some("compare") => compare(&cmd_name, args),
+        Some("fetch") => fetch(&cmd_name, args),
+        Some("submit") => submit(&cmd_name, args),
+        Some("serve") => serve(&cmd_name, args),
+        Some("new") => new_day(&cmd_name, args),
+        Some("samples") => extract_samples(&cmd_name, args),
+        _ => print_usage_exit(&cmd_name)
+    }
+}
+
+fn print_usage_exit(me: &str) -> ! {
+    println!("{me} gen <day> [--seed n] [--size n] [--out dir]");
+    println!("{me} run <day> [-p2] [--runs n] [--history file] input.txt");
+    println!("{me} compare <base revision> [revision] [--threshold percent] [--history file]");
+    println!("{me} fetch <day>");
+    println!("{me} submit <day> <part> <answer> [--yes]");
+    println!("{me} serve [--port n] [--session s] [--seed n] [--size n]");
+    println!("{me} new <day>");
+    println!("{me} samples <day> puzzle.html [--out dir] [--force]");
+    process::exit(1)
+}
+
+/// Print a generated input, or write it to a directory along with its
+/// answers in `tests.json`
+fn gen(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let mut day = None;
+    let mut seed = 0;
+    let mut size = 100;
+    let mut out_dir = None;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(cmd_name));
+
+        match a.as_str() {
+            "--seed" => seed = value().parse().unwrap_or_else(|_| print_usage_exit(cmd_name)),
+            "--size" => size = value().parse().unwrap_or_else(|_| print_usage_exit(cmd_name)),
+            "--out" => out_dir = Some(value()),
+            _ => day = Some(parse_day(&a).unwrap_or_else(|| print_usage_exit(cmd_name)))
+        }
+    }
+
+    let day = day.unwrap_or_else(|| print_usage_exit(cmd_name));
+
+    let generated = generate::generate(day, seed, size).unwrap_or_else(|| {
+        eprintln!("no generator for day{day}, available days are {:?}", generate::DAYS);
+        process::exit(1)
+    });
+
+    match out_dir {
+        Some(dir) => {
+            let file_name = format!("gen-{seed}-{size}.txt");
+            write_generated(Path::new(&dir), &file_name, &generated);
+            println!("{dir}/{file_name}");
+        },
+        None => print!("{}", generated.input)
+    }
+}
+
+/// Day number from `8` or `day8`
+fn parse_day(arg: &str) -> Option<u32> {
+    arg.strip_prefix("day").unwrap_or(arg).parse().ok()
+}
+
+fn write_generated(dir: &Path, file_name: &str, generated: &Generated) {
+    fs::create_dir_all(dir).expect("output dir should be writable");
+    fs::write(dir.join(file_name), &generated.input).expect("input file should be writable");
+
+    let answers: Vec<_> = [(1, &generated.part1), (2, &generated.part2)].into_iter()
+        .filter_map(|(part, answer)| answer.as_deref().map(|answer| (part, answer)))
+        .collect();
+
+    fixtures::add_answers(dir, file_name, &answers).expect("tests.json should be writable");
+}
+
+/// Run a day with profiling and add each run to the history
+fn run(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let mut day = None;
+    let mut part = 1;
+    let mut runs = 1;
+    let mut history_file = None;
+    let mut input = None;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(cmd_name));
+
+        match a.as_str() {
+            "-p2" => part = 2,
+            "--runs" => runs = value().parse().unwrap_or_else(|_| print_usage_exit(cmd_name)),
+            "--history" => history_file = Some(PathBuf::from(value())),
+            _ if day.is_none() => day = Some(parse_day(&a).unwrap_or_else(|| print_usage_exit(cmd_name))),
+            _ => input = Some(a)
+        }
+    }
+
+    let (Some(day), Some(input)) = (day, input) else {
+        print_usage_exit(cmd_name)
+    };
+
+    let data = fs::read(&input).expect("input file should exist");
+    let input_hash = history::hash_input(&data);
+    // cargo runs the day from the workspace root
+    let input_path = Path::new(&input).canonicalize().expect("input file should exist");
+
+    let revision = runner::revision().unwrap_or_else(|| "unknown".into());
+    let history_file = history_file.unwrap_or_else(|| runner::workspace_root().join(HISTORY_FILE));
+
+    for _ in 0..runs {
+        let result = runner::run(day, part, &input_path).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            process::exit(1)
+        });
+
+        let record = Record {
+            day,
+            part,
+            input: input.clone(),
+            input_hash: input_hash.clone(),
+            revision: revision.clone(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default(),
+            answer: result.answer,
+            phases: result.phases
+        };
+
+        history::append(&history_file, &record).expect("history file should be writable");
+
+        println!("{}  {:.3} ms", record.answer, record.total_ns() as f64 / 1e6);
+    }
+}
+
+/// Compare the times of two revisions in the history, exiting with an error
+/// if any solver got slower by more than the threshold
+fn compare(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let mut revisions = vec![];
+    let mut threshold = 10.0;
+    let mut history_file = None;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(cmd_name));
+
+        match a.as_str() {
+            "--threshold" => {
+                threshold = value().trim_end_matches('%').parse()
+                    .unwrap_or_else(|_| print_usage_exit(cmd_name));
+            },
+            "--history" => history_file = Some(PathBuf::from(value())),
+            _ => revisions.push(a)
+        }
+    }
+
+    let history_file = history_file.unwrap_or_else(|| runner::workspace_root().join(HISTORY_FILE));
+    let records = history::load(&history_file).unwrap_or_else(|err| {
+        eprintln!("Couldn't read {}: {err}", history_file.display());
+        process::exit(1)
+    });
+
+    // compare against the latest run by default
+    let (base, new) = match &revisions[..] {
+        [base] => match records.last() {
+            Some(latest) => (base.clone(), latest.revision.clone()),
+            None => (base.clone(), String::new())
+        },
+        [base, new] => (base.clone(), new.clone()),
+        _ => print_usage_exit(cmd_name)
+    };
+
+    let resolve = |revision: &str| history::resolve_revision(&records, revision).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1)
+    });
+    let (base, new) = (resolve(&base), resolve(&new));
+
+    let comparisons = history::compare(&records, &base, &new);
+    if comparisons.is_empty() {
+        eprintln!("No solver and input has runs at both {base} and {new}");
+        process::exit(1);
+    }
+
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let slower = comparison.is_regression(threshold / 100.0);
+        if slower {
+            regressions += 1;
+        }
+
+        println!(
+            "day{} part {} {:<24} {:>10.3} ms -> {:>10.3} ms {:>+8.1}%{}",
+            comparison.day,
+            comparison.part,
+            comparison.input,
+            comparison.base_ns as f64 / 1e6,
+            comparison.new_ns as f64 / 1e6,
+            comparison.change() * 100.0,
+            if slower { "  SLOWER" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} solver(s) got more than {threshold}% slower");
+        process::exit(1);
+    }
+}
+
+fn client_or_exit() -> Client {
+    Client::from_env().unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1)
+    })
+}
+
+/// Download the day's input into its data directory, unless it's already there
+fn fetch(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let day = args.next()
+        .and_then(|a| parse_day(&a))
+        .unwrap_or_else(|| print_usage_exit(cmd_name));
+
+    let (path, fetched) = client_or_exit()
+        .cached_input(&runner::data_dir(day), day)
+        .unwrap_or_else(|err| {
+            eprintln!("{err}");
+            process::exit(1)
+        });
+
+    if !fetched {
+        eprintln!("Already downloaded");
+    }
+    println!("{}", path.display());
+}
+
+/// Submit an answer unless the ledger shows it can't be right, exiting with
+/// an error unless it's correct
+fn submit(cmd_name: &str, args: impl Iterator<Item = String>) {
+    let mut positional = vec![];
+    let mut yes = false;
+
+    for a in args {
+        match a.as_str() {
+            "--yes" => yes = true,
+            _ => positional.push(a)
+        }
+    }
+
+    let (Some(day), Some(part), Some(answer)) = (
+        positional.first().and_then(|a| parse_day(a)),
+        positional.get(1).and_then(|a| a.parse::<u32>().ok()).filter(|part| *part == 1 || *part == 2),
+        positional.get(2).map(|a| a.trim())
+    ) else {
+        print_usage_exit(cmd_name)
+    };
+
+    let dir = runner::data_dir(day);
+    let ledger_file = dir.join(LEDGER_FILE);
+    let submissions = ledger::load(&ledger_file).unwrap_or_else(|err| {
+        eprintln!("Couldn't read {}: {err}", ledger_file.display());
+        process::exit(1)
+    });
+
+    if let Err(refusal) = ledger::check(&submissions, part, answer) {
+        eprintln!("Not submitting: {refusal}");
+        process::exit(1);
+    }
+
+    let verdict = client_or_exit()
+        .submit(day, part, answer)
+        .unwrap_or_else(|err| {
+            eprintln!("{err}");
+            process::exit(1)
+        });
+
+    let submission = Submission {
+        part,
+        answer: answer.into(),
+        verdict,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+    };
+    ledger::append(&ledger_file, &submission).expect("ledger file should be writable");
+
+    println!("{verdict}");
+    if verdict != Verdict::Correct {
+        process::exit(1);
+    }
+
+    if yes || confirm(&format!("Add part{part} = {answer} for input.txt to tests.json?")) {
+        fixtures::add_answers(&dir, "input.txt", &[(part, answer)])
+            .expect("tests.json should be writable");
+    }
+}
+
+/// Ask a yes/no question on stderr, anything but yes is no
+fn confirm(question: &str) -> bool {
+    eprint!("{question} [y/N] ");
+
+    let mut reply = String::new();
+    io::stdin().read_line(&mut reply).is_ok()
+        && matches!(reply.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Run the local stand-in for the puzzle site, serving generated puzzles
+fn serve(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let mut port = 8080;
+    let mut session = "mock".to_string();
+    let mut seed = 0;
+    let mut size = 100;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(cmd_name));
+
+        match a.as_str() {
+            "--port" => port = value().parse().unwrap_or_else(|_| print_usage_exit(cmd_name)),
+            "--session" => session = value(),
+            "--seed" => seed = value().parse().unwrap_or_else(|_| print_usage_exit(cmd_name)),
+            "--size" => size = value().parse().unwrap_or_else(|_| print_usage_exit(cmd_name)),
+            _ => print_usage_exit(cmd_name)
+        }
+    }
+
+    let puzzles = MockServer::generated_puzzles(seed, size);
+    let server = MockServer::start(&format!("127.0.0.1:{port}"), &session, puzzles)
+        .unwrap_or_else(|err| {
+            eprintln!("Couldn't start server: {err}");
+            process::exit(1)
+        });
+
+    eprintln!("Serving days {:?}, use with:", generate::DAYS);
+    println!("AOC_URL={} AOC_SESSION={session}", server.url());
+
+    server.wait();
+}
+
+/// Create a day crate from the templates and add it to the workspace
+fn new_day(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let day = args.next()
+        .and_then(|a| parse_day(&a))
+        .unwrap_or_else(|| print_usage_exit(cmd_name));
+
+    let root = runner::workspace_root();
+    let written = scaffold::create(&root, day).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1)
+    });
+
+    for path in written {
+        println!("{}", path.strip_prefix(&root).unwrap_or(&path).display());
+    }
+}
+
+/// Write the examples from a saved puzzle page as sample files, with their
+/// answers in `tests.json`
+fn extract_samples(cmd_name: &str, mut args: impl Iterator<Item = String>) {
+    let mut day = None;
+    let mut page = None;
+    let mut out_dir = None;
+    let mut force = false;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(cmd_name));
+
+        match a.as_str() {
+            "--out" => out_dir = Some(PathBuf::from(value())),
+            "--force" => force = true,
+            _ if day.is_none() => day = Some(parse_day(&a).unwrap_or_else(|| print_usage_exit(cmd_name))),
+            _ => page = Some(a)
+        }
+    }
+
+    let (Some(day), Some(page)) = (day, page) else {
+        print_usage_exit(cmd_name)
+    };
+
+    let html = fs::read_to_string(&page).unwrap_or_else(|err| {
+        eprintln!("Couldn't read {page}: {err}");
+        process::exit(1)
+    });
+
+    let found = samples::extract(&html);
+    if found.is_empty() {
+        eprintln!("No <pre><code> blocks in {page}");
+        process::exit(1);
+    }
+
+    let dir = out_dir.unwrap_or_else(|| runner::data_dir(day));
+    let files = samples::write(&dir, &found, force).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1)
+    });
+
+    for (file, sample) in files.iter().zip(&found) {
+        let answers: Vec<_> = [("part1", &sample.part1), ("part2", &sample.part2)].into_iter()
+            .filter_map(|(part, answer)| answer.as_ref().map(|answer| format!("{part}={answer}")))
+            .collect();
+
+        println!("{}  {}", dir.join(file).display(), answers.join(" "));
+    }
+}
@@ -0,0 +1,191 @@
+//! Local stand-in for the puzzle site, serving the same input and answer
+//! endpoints so fetching and submitting can be tried and tested offline
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    io,
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle}
+};
+
+use tiny_http::{Method, Request, Response, Server};
+
+use crate::{client::YEAR, generate::{self, Generated}};
+
+/// Puzzles the server knows, and which parts have been solved
+struct State {
+    session: String,
+    puzzles: HashMap<u32, Generated>,
+    solved: HashSet<(u32, u32)>,
+    requests: usize
+}
+
+pub struct MockServer {
+    server: Arc<Server>,
+    url: String,
+    state: Arc<Mutex<State>>,
+    handle: Option<JoinHandle<()>>
+}
+
+impl MockServer {
+    /// Serve the puzzles on `addr`, port 0 picks a free port. Requests need
+    /// the `session` cookie, like the real site.
+    pub fn start(addr: &str, session: &str, puzzles: HashMap<u32, Generated>) -> io::Result<Self> {
+        let server = Arc::new(Server::http(addr).map_err(io::Error::other)?);
+
+        let url = match server.server_addr().to_ip() {
+            Some(addr) => format!("http://{addr}"),
+            None => return Err(io::Error::other("server should listen on an ip address"))
+        };
+
+        let state = Arc::new(Mutex::new(State {
+            session: session.into(),
+            puzzles,
+            solved: HashSet::new(),
+            requests: 0
+        }));
+
+        let handle = {
+            let server = server.clone();
+            let state = state.clone();
+
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let mut state = state.lock().expect("mock state should not be poisoned");
+                    state.requests += 1;
+
+                    // the client hanging up early isn't the server's problem
+                    let _ = state.handle(request);
+                }
+            })
+        };
+
+        Ok(MockServer { server, url, state, handle: Some(handle) })
+    }
+
+    /// Puzzles from the generators for every day that has one
+    pub fn generated_puzzles(seed: u64, size: usize) -> HashMap<u32, Generated> {
+        generate::DAYS.iter()
+            .filter_map(|day| generate::generate(*day, seed, size).map(|g| (*day, g)))
+            .collect()
+    }
+
+    /// Base url to use in place of the puzzle site
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Number of requests served so far
+    pub fn requests(&self) -> usize {
+        self.state.lock().expect("mock state should not be poisoned").requests
+    }
+
+    /// Serve until the process is stopped
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl State {
+    fn handle(&mut self, mut request: Request) -> io::Result<()> {
+        let path: Vec<String> = request.url()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect();
+
+        let day = match &path[..] {
+            [year, d, day, _] if *year == YEAR.to_string() && d == "day" => day.parse::<u32>().ok(),
+            _ => None
+        }.filter(|day| self.puzzles.contains_key(day));
+
+        let Some(day) = day else {
+            return request.respond(Response::from_string("404 Not Found").with_status_code(404));
+        };
+
+        if !self.has_session(&request) {
+            let message = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+            return request.respond(Response::from_string(message).with_status_code(400));
+        }
+
+        match (request.method(), path[3].as_str()) {
+            (Method::Get, "input") => {
+                let input = self.puzzles[&day].input.clone();
+                request.respond(Response::from_string(input))
+            },
+            (Method::Post, "answer") => {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body)?;
+
+                let form: HashMap<_, _> = form_urlencoded::parse(body.as_bytes()).collect();
+                let part = form.get("level").and_then(|level| level.parse::<u32>().ok());
+                let answer = form.get("answer").map(|answer| answer.trim().to_string());
+
+                let message = match (part, answer) {
+                    (Some(part @ (1 | 2)), Some(answer)) => self.check(day, part, &answer),
+                    _ => return request.respond(Response::from_string("400 Bad Request").with_status_code(400))
+                };
+
+                let page = format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>\n");
+                let header = "Content-Type: text/html; charset=utf-8".parse::<tiny_http::Header>()
+                    .expect("content type header should be valid");
+
+                request.respond(Response::from_string(page).with_header(header))
+            },
+            _ => request.respond(Response::from_string("404 Not Found").with_status_code(404))
+        }
+    }
+
+    fn has_session(&self, request: &Request) -> bool {
+        request.headers()
+            .iter()
+            .filter(|header| header.field.equiv("Cookie"))
+            .flat_map(|header| header.value.as_str().split(';'))
+            .any(|cookie| cookie.trim().strip_prefix("session=") == Some(self.session.as_str()))
+    }
+
+    /// Answer page message, with the same wording as the real site
+    fn check(&mut self, day: u32, part: u32, answer: &str) -> String {
+        let unlocked = part == 1 || self.solved.contains(&(day, 1));
+        if !unlocked || self.solved.contains(&(day, part)) {
+            return "You don't seem to be solving the right level.  Did you already complete it?".into();
+        }
+
+        let expected = match part {
+            1 => &self.puzzles[&day].part1,
+            _ => &self.puzzles[&day].part2
+        };
+
+        let Some(expected) = expected else {
+            return "That's not the right answer.".into();
+        };
+
+        if answer == expected {
+            self.solved.insert((day, part));
+            return "That's the right answer!  You are one gold star closer to saving the day.".into();
+        }
+
+        let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(a), Ok(e)) => match a.cmp(&e) {
+                Ordering::Greater => "; your answer is too high",
+                _ => "; your answer is too low"
+            },
+            _ => ""
+        };
+
+        format!("That's not the right answer{hint}.  Please wait one minute before trying again.")
+    }
+}
@@ -0,0 +1,154 @@
+//! Running the day binaries through cargo and reading their answers and
+//! profiles
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus}
+};
+
+use serde::Deserialize;
+
+use crate::history::PhaseTiming;
+
+/// Root of the workspace the runner was built in
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc crate should be in the workspace")
+        .to_path_buf()
+}
+
+/// Directory holding a day's inputs and `tests.json`
+pub fn data_dir(day: u32) -> PathBuf {
+    workspace_root().join("tests/data").join(format!("day{day}"))
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub answer: String,
+    pub phases: Vec<PhaseTiming>
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    Spawn(io::Error),
+    Failed {
+        status: ExitStatus,
+        stderr: String
+    },
+    /// The day ran but didn't print a profile, so it may not support `--profile-json`
+    NoProfile {
+        stderr: String
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Spawn(err) => write!(f, "Couldn't run cargo: {err}"),
+            RunError::Failed { status, stderr } => write!(f, "Day failed with {status}:\n{stderr}"),
+            RunError::NoProfile { stderr } => write!(f, "Day didn't print a profile:\n{stderr}")
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ProfileOutput {
+    phases: Vec<PhaseTiming>
+}
+
+/// Cargo command that runs one part of a day in release mode
+pub fn command(day: u32, part: u32, input: &Path, extra_args: &[&str]) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(workspace_root())
+        .args(["run", "-q", "--release", "-p", &format!("day{day}")]);
+
+    // day7 picks the part 2 rules at compile time
+    if day == 7 && part == 2 {
+        cmd.args(["--features", "wildcard"]);
+    }
+
+    cmd.arg("--");
+    if part == 2 && day != 7 {
+        cmd.arg("-p2");
+    }
+
+    cmd.args(extra_args).arg(input);
+    cmd
+}
+
+/// Run one part of a day, returning the answer and the time of each phase
+pub fn run(day: u32, part: u32, input: &Path) -> Result<Run, RunError> {
+    let output = command(day, part, input, &["--profile-json"])
+        .output()
+        .map_err(RunError::Spawn)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    if !output.status.success() {
+        return Err(RunError::Failed { status: output.status, stderr });
+    }
+
+    let Some(profile) = stderr.lines()
+        .rev()
+        .find_map(|line| serde_json::from_str::<ProfileOutput>(line).ok()) else {
+        return Err(RunError::NoProfile { stderr });
+    };
+
+    let answer = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Ok(Run { answer, phases: profile.phases })
+}
+
+/// Commit hash of the workspace, with `-dirty` added if tracked files have
+/// changed, or None if it isn't a git checkout
+pub fn revision() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(workspace_root())
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let hash = git(&["rev-parse", "HEAD"])?;
+    let changes = git(&["status", "--porcelain", "--untracked-files=no"])?;
+
+    Some(if changes.is_empty() { hash } else { format!("{hash}-dirty") })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+    }
+
+    #[test]
+    fn part_args() {
+        let input = Path::new("in.txt");
+
+        assert_eq!(
+            args(&command(8, 2, input, &["--time"])).join(" "),
+            "run -q --release -p day8 -- -p2 --time in.txt"
+        );
+        assert_eq!(
+            args(&command(7, 2, input, &[])).join(" "),
+            "run -q --release -p day7 --features wildcard -- in.txt"
+        );
+        assert_eq!(
+            args(&command(3, 1, input, &[])).join(" "),
+            "run -q --release -p day3 -- in.txt"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Sample inputs and their answers from a saved puzzle page, so they don't
+//! have to be copied into the data directory by hand
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf}
+};
+
+use crate::{fixtures, html};
+
+const BLOCK: &str = "<pre><code>";
+const BLOCK_END: &str = "</code></pre>";
+const ANSWER: &str = "<code><em>";
+const ANSWER_END: &str = "</em></code>";
+
+/// Example from the page, with the answers given for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>
+}
+
+impl Sample {
+    fn answers(&self) -> Vec<(u32, &str)> {
+        [(1, &self.part1), (2, &self.part2)].into_iter()
+            .filter_map(|(part, answer)| answer.as_deref().map(|answer| (part, answer)))
+            .collect()
+    }
+}
+
+/// Every `<pre><code>` block on the page in order, identical blocks are
+/// only kept once. Blocks with emphasized text walk through the working
+/// rather than being inputs, so they're skipped.
+///
+/// Each part of the puzzle is its own `<article>`. The last emphasized code
+/// after a block, and before the next one, is taken as the answer for that
+/// block. Part 2 answers before any block of its own go to the first block
+/// of the page, since part 2 usually goes back to the main example.
+pub fn extract(page: &str) -> Vec<Sample> {
+    let mut articles: Vec<&str> = page.split("<article").skip(1).collect();
+    if articles.is_empty() {
+        articles.push(page);
+    }
+
+    let mut samples: Vec<Sample> = vec![];
+    let mut current = None;
+
+    for (i, article) in articles.iter().take(2).enumerate() {
+        let part = i + 1;
+        let mut answer: Option<String> = None;
+
+        if part == 2 && !samples.is_empty() {
+            current = Some(0);
+        }
+
+        let flush = |samples: &mut Vec<Sample>, current: Option<usize>, answer: &mut Option<String>| {
+            if let (Some(index), Some(answer)) = (current, answer.take()) {
+                let sample = &mut samples[index];
+                match part {
+                    1 => sample.part1 = Some(answer),
+                    _ => sample.part2 = Some(answer)
+                }
+            }
+        };
+
+        let mut rest = *article;
+        loop {
+            let next_block = rest.find(BLOCK);
+            let next_answer = rest.find(ANSWER);
+
+            match (next_block, next_answer) {
+                (Some(b), a) if a.is_none_or(|a| b < a) => {
+                    let content = &rest[b + BLOCK.len()..];
+                    let end = content.find(BLOCK_END).unwrap_or(content.len());
+                    rest = &content[end..];
+
+                    if content[..end].contains("<em>") {
+                        continue;
+                    }
+
+                    let input = html::text(&content[..end]);
+
+                    flush(&mut samples, current, &mut answer);
+
+                    current = Some(match samples.iter().position(|s| s.input == input) {
+                        Some(index) => index,
+                        None => {
+                            samples.push(Sample { input, part1: None, part2: None });
+                            samples.len() - 1
+                        }
+                    });
+                },
+                (_, Some(a)) => {
+                    let content = &rest[a + ANSWER.len()..];
+                    let end = content.find(ANSWER_END).unwrap_or(content.len());
+                    let text = html::text(&content[..end]).trim().to_string();
+
+                    if !text.is_empty() {
+                        answer = Some(text);
+                    }
+
+                    rest = &content[end..];
+                },
+                _ => break
+            }
+        }
+
+        flush(&mut samples, current, &mut answer);
+    }
+
+    samples
+}
+
+#[derive(Debug)]
+pub enum SampleError {
+    /// Sample file is already there with different content
+    Changed(PathBuf),
+    Io(io::Error)
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleError::Changed(path) => write!(f, "{} already exists with a different sample", path.display()),
+            SampleError::Io(err) => write!(f, "{err}")
+        }
+    }
+}
+
+impl From<io::Error> for SampleError {
+    fn from(err: io::Error) -> Self {
+        SampleError::Io(err)
+    }
+}
+
+/// File name of the nth sample, `sample.txt` then `sample2.txt` and so on
+pub fn file_name(index: usize) -> String {
+    match index {
+        0 => "sample.txt".into(),
+        i => format!("sample{}.txt", i + 1)
+    }
+}
+
+/// Write each sample to `dir` and add its answers to `tests.json`, returning
+/// the file names written. Unless `force` is set nothing is written if an
+/// existing sample file has different content.
+pub fn write(dir: &Path, samples: &[Sample], force: bool) -> Result<Vec<String>, SampleError> {
+    let files: Vec<_> = (0..samples.len()).map(file_name).collect();
+
+    if !force {
+        for (name, sample) in files.iter().zip(samples) {
+            let path = dir.join(name);
+            match fs::read_to_string(&path) {
+                Ok(existing) if existing != sample.input => return Err(SampleError::Changed(path)),
+                _ => ()
+            }
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+
+    for (name, sample) in files.iter().zip(samples) {
+        fs::write(dir.join(name), &sample.input)?;
+
+        let answers = sample.answers();
+        if !answers.is_empty() {
+            fixtures::add_answers(dir, name, &answers)?;
+        }
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 0: Test ---</h2>
+<p>Here is an example:</p>
+<pre><code>1 2
+3 &lt;4&gt;
+</code></pre>
+<p>Working through it:</p>
+<pre><code><em>3</em>
+7
+</code></pre>
+<p>The first line is <code><em>3</em></code>, so the total is <code><em>10</em></code>.</p>
+<p>Another example:</p>
+<pre><code>5 5
+</code></pre>
+<p>This one gives <code><em>10</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>For the first example, <code>product</code> gives <code><em>24</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn extract_samples() {
+        let samples = extract(PAGE);
+
+        assert_eq!(samples, vec![
+            Sample { input: "1 2\n3 <4>\n".into(), part1: Some("10".into()), part2: Some("24".into()) },
+            Sample { input: "5 5\n".into(), part1: Some("10".into()), part2: None }
+        ]);
+    }
+
+    #[test]
+    fn part2_example() {
+        let page = "<article><pre><code>a\n</code></pre><code><em>1</em></code></article>\
+                    <article><pre><code>b\n</code></pre><code><em>2</em></code>\
+                    <pre><code>a\n</code></pre><code><em>3</em></code></article>";
+
+        assert_eq!(extract(page), vec![
+            Sample { input: "a\n".into(), part1: Some("1".into()), part2: Some("3".into()) },
+            Sample { input: "b\n".into(), part1: None, part2: Some("2".into()) }
+        ]);
+    }
+
+    #[test]
+    fn write_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let samples = extract(PAGE);
+        assert_eq!(write(dir, &samples, false).unwrap(), ["sample.txt", "sample2.txt"]);
+        assert_eq!(fs::read_to_string(dir.join("sample2.txt")).unwrap(), "5 5\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("tests.json")).unwrap().split_whitespace().collect::<String>(),
+            r#"{"sample.txt":{"part1":"10","part2":"24"},"sample2.txt":{"part1":"10"}}"#
+        );
+
+        // same samples again are fine, a different one isn't
+        assert!(write(dir, &samples, false).is_ok());
+        let other = [Sample { input: "x\n".into(), part1: None, part2: None }];
+        assert!(matches!(write(dir, &other, false), Err(SampleError::Changed(_))));
+        assert!(write(dir, &other, true).is_ok());
+    }
+}
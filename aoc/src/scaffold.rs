@@ -0,0 +1,156 @@
+//! Creating a new day crate from the templates, added to the workspace with
+//! an empty `tests.json` so it starts out like the existing days
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf}
+};
+
+use crate::fixtures::INDEX_FILE;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+const TEST_SH: &str = include_str!("../templates/test.sh.tmpl");
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    /// Workspace manifest doesn't have a `members = [...]` list
+    NoMembers,
+    Io(io::Error)
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::NoMembers => write!(f, "Workspace Cargo.toml should have a members list"),
+            ScaffoldError::Io(err) => write!(f, "{err}")
+        }
+    }
+}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(err: io::Error) -> Self {
+        ScaffoldError::Io(err)
+    }
+}
+
+fn render(template: &str, day: u32) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// Create `day<N>` in the workspace at `root`, returning the files written
+pub fn create(root: &Path, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let name = format!("day{day}");
+    let crate_dir = root.join(&name);
+    if crate_dir.exists() {
+        return Err(ScaffoldError::Exists(crate_dir));
+    }
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = add_member(&fs::read_to_string(&manifest_path)?, &name)
+        .ok_or(ScaffoldError::NoMembers)?;
+
+    fs::create_dir_all(crate_dir.join("src"))?;
+
+    let mut written = vec![];
+    for (path, template) in [
+        ("Cargo.toml", CARGO_TOML),
+        ("src/lib.rs", LIB_RS),
+        ("src/main.rs", MAIN_RS),
+        ("test.sh", TEST_SH)
+    ] {
+        let path = crate_dir.join(path);
+        fs::write(&path, render(template, day))?;
+        written.push(path);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(crate_dir.join("test.sh"), fs::Permissions::from_mode(0o755))?;
+    }
+
+    // the data directory may already have a fetched or generated input
+    let data_dir = root.join("tests/data").join(&name);
+    let index = data_dir.join(INDEX_FILE);
+    if !index.exists() {
+        fs::create_dir_all(&data_dir)?;
+        fs::write(&index, "{}\n")?;
+        written.push(index);
+    }
+
+    fs::write(&manifest_path, manifest)?;
+    written.push(manifest_path);
+
+    Ok(written)
+}
+
+/// Workspace manifest with the member added to its `members` list, after
+/// the crates that aren't days and in order of day number. None if the
+/// manifest doesn't have a list.
+pub fn add_member(manifest: &str, member: &str) -> Option<String> {
+    let start = manifest.find("members = [")? + "members = [".len();
+    let end = start + manifest[start..].find(']')?;
+
+    let mut members: Vec<String> = manifest[start..end]
+        .split(',')
+        .map(|m| m.trim().trim_matches('"').to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+
+    if !members.iter().any(|m| m == member) {
+        members.push(member.into());
+    }
+
+    // day3_rust sorts as day 3, crates that aren't days go first
+    let day_number = |m: &str| -> Option<u32> {
+        let digits: String = m.strip_prefix("day")?
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    };
+    members.sort_by_key(|m| day_number(m));
+
+    let list: Vec<_> = members.iter().map(|m| format!("    \"{m}\"")).collect();
+
+    Some(format!("{}\n{}\n{}", &manifest[..start], list.join(",\n"), &manifest[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"common\",\n    \"day3_rust\",\n    \"day10\"\n]\nexclude = [\"fuzz\"]\n";
+
+    #[test]
+    fn members_in_day_order() {
+        assert_eq!(
+            add_member(MANIFEST, "day4").unwrap(),
+            "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"common\",\n    \"day3_rust\",\n    \"day4\",\n    \"day10\"\n]\nexclude = [\"fuzz\"]\n"
+        );
+        assert_eq!(add_member(MANIFEST, "day10").unwrap(), MANIFEST);
+        assert_eq!(add_member("[package]\nname = \"x\"\n", "day4"), None);
+    }
+
+    #[test]
+    fn create_day() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+
+        let written = create(root, 11).unwrap();
+        assert_eq!(written.len(), 6);
+
+        let main = fs::read_to_string(root.join("day11/src/main.rs")).unwrap();
+        assert!(main.contains("use day11::{part1, part2};"));
+        assert!(main.contains("Output::from_args(11, args)"));
+        assert_eq!(fs::read_to_string(root.join("tests/data/day11/tests.json")).unwrap(), "{}\n");
+        assert!(fs::read_to_string(root.join("Cargo.toml")).unwrap().contains("\"day11\"\n]"));
+
+        assert!(matches!(create(root, 11), Err(ScaffoldError::Exists(_))));
+    }
+}
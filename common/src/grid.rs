@@ -0,0 +1,317 @@
+use std::{fmt, ops::{Add, Index, IndexMut}};
+
+/// Position of a cell in a grid, where `x` is the column and `y` is the row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Offsets to the orthogonal neighbours, clockwise from north
+const NEIGHBOURS_4: [Point; 4] = [
+    Point { x: 0, y: -1 },
+    Point { x: 1, y: 0 },
+    Point { x: 0, y: 1 },
+    Point { x: -1, y: 0 }
+];
+
+/// Offsets to the orthogonal and diagonal neighbours, clockwise from north west
+const NEIGHBOURS_8: [Point; 8] = [
+    Point { x: -1, y: -1 },
+    Point { x: 0, y: -1 },
+    Point { x: 1, y: -1 },
+    Point { x: 1, y: 0 },
+    Point { x: 1, y: 1 },
+    Point { x: 0, y: 1 },
+    Point { x: -1, y: 1 },
+    Point { x: -1, y: 0 }
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// Line has a different length to the first line, line numbers start at 1
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize
+    }
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "Grid has no cells"),
+            GridError::RaggedLine { line, expected, found } => {
+                write!(f, "Line {line} has {found} cells, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError { }
+
+/// Rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    cols: usize,
+    rows: usize
+}
+
+impl<T> Grid<T> {
+    /// Build grid from lines of text, converting each char to a cell.
+    /// Every line must be the same length as the first line.
+    pub fn parse<F>(data: &str, mut f: F) -> Result<Self, GridError>
+        where F: FnMut(char) -> T
+    {
+        let mut cells = vec![];
+        let mut cols = None;
+        let mut rows = 0;
+
+        for (i, line) in data.lines().enumerate() {
+            let len = line.chars().count();
+
+            match cols {
+                None => cols = Some(len),
+                Some(expected) if expected != len => {
+                    return Err(GridError::RaggedLine { line: i + 1, expected, found: len });
+                },
+                _ => ()
+            }
+
+            cells.extend(line.chars().map(&mut f));
+            rows += 1;
+        }
+
+        match cols {
+            Some(cols) if cols > 0 => Ok(Grid { cells, cols, rows }),
+            _ => Err(GridError::Empty)
+        }
+    }
+
+    /// New grid of the same size, with each cell converted by the function
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+        where F: FnMut(&T) -> U
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            cols: self.cols,
+            rows: self.rows
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// All cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0 && (point.x as usize) < self.cols &&
+            point.y >= 0 && (point.y as usize) < self.rows
+    }
+
+    pub fn index_to_point(&self, index: usize) -> Point {
+        Point::new((index % self.cols) as i32, (index / self.cols) as i32)
+    }
+
+    /// Returns the index of the point in the cells, or None if the point is
+    /// outside of the grid
+    pub fn point_to_index(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.cols + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.point_to_index(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.point_to_index(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Cells of row `y`
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.cols .. (y + 1) * self.cols]
+    }
+
+    /// Iterate over the rows from top to bottom
+    pub fn rows_iter(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.cols)
+    }
+
+    pub fn rows_iter_mut(&mut self) -> std::slice::ChunksMut<'_, T> {
+        self.cells.chunks_mut(self.cols)
+    }
+
+    /// Cells of column `x`, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.cols)
+    }
+
+    /// Orthogonal neighbours of the point that are inside the grid
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &NEIGHBOURS_4)
+    }
+
+    /// Orthogonal and diagonal neighbours of the point that are inside the grid
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &NEIGHBOURS_8)
+    }
+
+    fn neighbours<'a>(&'a self, point: Point, offsets: &'static [Point])
+        -> impl Iterator<Item = Point> + 'a
+    {
+        offsets.iter()
+            .map(move |offset| point + *offset)
+            .filter(|p| self.contains(*p))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("point should be inside grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("point should be inside grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows_iter() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = grid();
+        assert_eq!(grid.cols(), 3);
+        assert_eq!(grid.rows(), 2);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        assert_eq!(
+            Grid::parse("abc\nde\nfgh", |c| c),
+            Err(GridError::RaggedLine { line: 2, expected: 3, found: 2 })
+        );
+        assert_eq!(Grid::parse("", |c| c), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn points() {
+        let grid = grid();
+        assert_eq!(grid.index_to_point(4), Point::new(1, 1));
+        assert_eq!(grid.point_to_index(Point::new(2, 1)), Some(5));
+        assert_eq!(grid.point_to_index(Point::new(3, 0)), None);
+        assert_eq!(grid.point_to_index(Point::new(0, 2)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid[Point::new(2, 0)], 'c');
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.rows_iter().count(), 2);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).row(0), &['A', 'B', 'C']);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        let corner: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+
+        let middle: String = grid.neighbours8(Point::new(1, 0))
+            .map(|p| grid[p])
+            .collect();
+        assert_eq!(middle, "cfeda");
+    }
+}
@@ -0,0 +1,68 @@
+use std::{error, fmt, fs::File, io::{self, BufRead, BufReader}, path::Path};
+
+/// Open the input file for reading line by line, rather than loading the
+/// whole file into memory
+pub fn try_open<P: AsRef<Path>>(path: P) -> io::Result<BufReader<File>> {
+    File::open(path).map(BufReader::new)
+}
+
+/// Lines of the input without line endings, only one line is held in memory
+/// at a time. Reading can still fail partway through, such as on a line that
+/// isn't UTF-8.
+pub fn lines<R: BufRead>(input: R) -> impl Iterator<Item = io::Result<String>> {
+    input.lines()
+}
+
+/// Error from a solver that parses its input as it reads it, either reading
+/// a line failed or the line didn't parse
+#[derive(Debug)]
+pub enum LineError<E> {
+    Read(io::Error),
+    Parse(E)
+}
+
+impl<E> LineError<E> {
+    /// Kind of error to report with `Output::fail`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LineError::Read(_) => "read",
+            LineError::Parse(_) => "load"
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for LineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineError::Read(err) => write!(f, "{err}"),
+            LineError::Parse(err) => write!(f, "{err}")
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> error::Error for LineError<E> { }
+
+impl<E> From<io::Error> for LineError<E> {
+    fn from(err: io::Error) -> Self {
+        LineError::Read(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_lines() {
+        let lines: Vec<_> = lines("a\r\nb\nc".as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(lines, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut lines = lines(&b"a\n\xff\n"[..]);
+
+        assert_eq!(lines.next().unwrap().unwrap(), "a");
+        assert_eq!(lines.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
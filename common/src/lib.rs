@@ -0,0 +1,7 @@
+//! Code shared between the days of the puzzle
+
+pub mod alloc;
+pub mod grid;
+pub mod input;
+pub mod output;
+pub mod profile;
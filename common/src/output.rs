@@ -0,0 +1,160 @@
+use std::{
+    fmt, panic, process,
+    sync::{Arc, Mutex},
+    time::Instant
+};
+
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    /// One json object per answer or error, for scripts to read
+    Json
+}
+
+/// What a run is answering, shared with the panic hook so a panic can be
+/// reported as a structured error
+#[derive(Debug, Clone)]
+struct Context {
+    day: u32,
+    part: u32,
+    input: Option<String>
+}
+
+/// Prints the answer, or an error, in the format picked with `--format`
+pub struct Output {
+    format: Format,
+    context: Arc<Mutex<Context>>,
+    start: Instant
+}
+
+impl Output {
+    /// Takes `--format text|json` out of the command line args, returning
+    /// the rest so each day can parse them as before
+    pub fn from_args(day: u32, mut args: impl Iterator<Item = String>) -> (Self, std::vec::IntoIter<String>) {
+        let mut format = Format::Text;
+        let mut rest = vec![];
+
+        while let Some(a) = args.next() {
+            if a == "--format" {
+                format = match args.next().as_deref() {
+                    Some("json") => Format::Json,
+                    Some("text") => Format::Text,
+                    other => {
+                        eprintln!("Unknown format {other:?}, expected text or json");
+                        process::exit(1)
+                    }
+                };
+            } else {
+                rest.push(a);
+            }
+        }
+
+        let output = Output {
+            format,
+            context: Arc::new(Mutex::new(Context { day, part: 1, input: None })),
+            start: Instant::now()
+        };
+
+        if format == Format::Json {
+            output.install_panic_hook();
+        }
+
+        (output, rest.into_iter())
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn set_part(&self, part: u32) {
+        self.context.lock().unwrap().part = part;
+    }
+
+    pub fn set_input(&self, input: &str) {
+        self.context.lock().unwrap().input = Some(input.into());
+    }
+
+    pub fn answer(&self, answer: impl fmt::Display) {
+        match self.format {
+            Format::Text => println!("{answer}"),
+            Format::Json => {
+                let mut object = self.context_json();
+                object["answer"] = answer.to_string().into();
+                object["elapsed"] = self.start.elapsed().as_secs_f64().into();
+                println!("{object}");
+            }
+        }
+    }
+
+    /// Report an error and exit, `kind` is a short name for the step that
+    /// failed such as `read` or `load`
+    pub fn fail(&self, kind: &str, message: impl fmt::Display) -> ! {
+        match self.format {
+            Format::Text => eprintln!("{message}"),
+            Format::Json => println!("{}", error_json(&self.context_json(), kind, &message.to_string()))
+        }
+        process::exit(1)
+    }
+
+    fn context_json(&self) -> Value {
+        context_json(&self.context.lock().unwrap())
+    }
+
+    /// Report panics, such as a parser unwrapping bad input, as errors
+    /// rather than plain text
+    fn install_panic_hook(&self) {
+        let context = Arc::clone(&self.context);
+
+        panic::set_hook(Box::new(move |info| {
+            let message = info.payload().downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| info.payload().downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".into());
+            let message = match info.location() {
+                Some(location) => format!("{message} at {location}"),
+                None => message
+            };
+
+            // the lock may be poisoned if the panic happened while holding it
+            let context = context.lock().map(|c| c.clone()).unwrap_or_else(|e| e.into_inner().clone());
+            println!("{}", error_json(&context_json(&context), "panic", &message));
+        }));
+    }
+}
+
+fn context_json(context: &Context) -> Value {
+    json!({
+        "day": context.day,
+        "part": context.part,
+        "input": context.input
+    })
+}
+
+fn error_json(context: &Value, kind: &str, message: &str) -> Value {
+    let mut object = context.clone();
+    object["error"] = json!({ "kind": kind, "message": message });
+    object
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_args() {
+        let args = ["me", "-p2", "--format", "json", "input.txt"].map(String::from);
+        let (output, rest) = Output::from_args(8, args.into_iter());
+
+        assert_eq!(output.format(), Format::Json);
+        assert_eq!(rest.collect::<Vec<_>>(), vec!["me", "-p2", "input.txt"]);
+    }
+
+    #[test]
+    fn error_object() {
+        let context = Context { day: 10, part: 2, input: Some("in.txt".into()) };
+        let error = error_json(&context_json(&context), "load", "Pipes should have start tile");
+
+        assert_eq!(error.to_string(), r#"{"day":10,"part":2,"input":"in.txt","error":{"kind":"load","message":"Pipes should have start tile"}}"#);
+    }
+}
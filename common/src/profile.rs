@@ -0,0 +1,171 @@
+use std::{
+    fmt,
+    time::{Duration, Instant}
+};
+
+use crate::alloc;
+
+/// Wall time and peak heap of each phase of a run, enabled by the `--time`
+/// and `--mem` args, or `--profile-json` for both as one line of json
+#[derive(Debug, Default)]
+pub struct Profile {
+    time: bool,
+    mem: bool,
+    json: bool,
+    phases: Vec<Phase>
+}
+
+#[derive(Debug)]
+struct Phase {
+    name: &'static str,
+    elapsed: Duration,
+    peak_heap: usize
+}
+
+impl Profile {
+    /// Takes the profile args out of the command line args, returning the
+    /// rest so each day can parse them as before
+    pub fn from_args(args: impl Iterator<Item = String>) -> (Self, std::vec::IntoIter<String>) {
+        let mut profile = Profile::default();
+
+        let rest: Vec<String> = args
+            .filter(|a| match a.as_str() {
+                "--time" => { profile.time = true; false },
+                "--mem" => { profile.mem = true; false },
+                "--profile-json" => { profile.json = true; false },
+                _ => true
+            })
+            .collect();
+
+        (profile, rest.into_iter())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.time || self.mem || self.json
+    }
+
+    /// Run one phase, such as reading, parsing or solving, and record it
+    pub fn phase<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        alloc::reset_peak();
+        let start = Instant::now();
+
+        let result = f();
+
+        self.phases.push(Phase {
+            name,
+            elapsed: start.elapsed(),
+            peak_heap: alloc::peak()
+        });
+
+        result
+    }
+
+    /// Print the phases to stderr, so stdout still only has the answer
+    pub fn report(&self) {
+        if self.json {
+            eprintln!("{}", self.to_json());
+        } else if self.is_enabled() {
+            eprint!("{self}");
+        }
+    }
+
+    /// Phases in the form `{"phases":[{"name":"read","elapsed_ns":1,"peak_heap":2}]}`
+    pub fn to_json(&self) -> String {
+        let phases: Vec<String> = self.phases.iter()
+            .map(|p| format!(
+                r#"{{"name":"{}","elapsed_ns":{},"peak_heap":{}}}"#,
+                p.name, p.elapsed.as_nanos(), p.peak_heap
+            ))
+            .collect();
+
+        format!(r#"{{"phases":[{}]}}"#, phases.join(","))
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = Phase {
+            name: "total",
+            elapsed: self.phases.iter().map(|p| p.elapsed).sum(),
+            peak_heap: self.phases.iter().map(|p| p.peak_heap).max().unwrap_or_default()
+        };
+
+        for phase in self.phases.iter().chain([&total]) {
+            write!(f, "{:<8}", phase.name)?;
+            if self.time {
+                write!(f, "{:>12.3} ms", phase.elapsed.as_secs_f64() * 1000.0)?;
+            }
+            if self.mem {
+                write!(f, "{:>12} peak heap", format_bytes(phase.peak_heap))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_args() {
+        let args = ["me", "--time", "-p2", "--mem", "input.txt"].map(String::from);
+        let (profile, rest) = Profile::from_args(args.into_iter());
+
+        assert!(profile.time && profile.mem);
+        assert_eq!(rest.collect::<Vec<_>>(), vec!["me", "-p2", "input.txt"]);
+    }
+
+    #[test]
+    fn report_columns() {
+        let mut profile = Profile { mem: true, ..Profile::default() };
+        profile.phase("parse", || ());
+
+        let report = profile.to_string();
+        assert!(report.starts_with("parse   "));
+        assert!(report.contains("\ntotal   "));
+        assert!(!report.contains("ms"));
+    }
+
+    #[test]
+    fn report_json() {
+        let profile = Profile {
+            json: true,
+            phases: vec![
+                Phase { name: "read", elapsed: Duration::from_nanos(15), peak_heap: 64 },
+                Phase { name: "solve", elapsed: Duration::from_micros(2), peak_heap: 0 }
+            ],
+            ..Profile::default()
+        };
+
+        assert_eq!(profile.to_json(), "{\"phases\":[\
+            {\"name\":\"read\",\"elapsed_ns\":15,\"peak_heap\":64},\
+            {\"name\":\"solve\",\"elapsed_ns\":2000,\"peak_heap\":0}]}");
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,42 @@
+use std::hint::black_box;
+
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day10::PipeArea;
+
+const SIZES: [usize; 3] = [10, 50, 200];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day10/load");
+
+    for (size, input) in sized_inputs(10, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| PipeArea::load(black_box(input)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day10/solve");
+
+    for (size, input) in sized_inputs(10, &SIZES) {
+        let area = PipeArea::load(&input).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("part1", size), &area, |b, area| {
+            b.iter(|| area.distance_map().max_distance())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &area, |b, area| {
+            b.iter(|| area.count_enclosed_tiles())
+        });
+        group.bench_with_input(BenchmarkId::new("part2_scanline", size), &area, |b, area| {
+            b.iter(|| area.count_enclosed_tiles_scanline())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
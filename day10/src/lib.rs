@@ -0,0 +1,616 @@
+//! Loops of pipes in a field of tiles
+
+use std::{collections::VecDeque, fmt};
+
+use common::grid::{Grid, GridError, Point};
+
+pub mod render;
+
+pub struct PipeArea {
+    pipes: Grid<PipeTile>,
+    start: usize
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    Grid(GridError),
+    UnknownTile {
+        at: Point,
+        tile: char
+    },
+    NoStart,
+    /// None of the pipes that could be under the start tile form a loop, with
+    /// the reason walking the loop failed for each pipe
+    NoLoop {
+        start: Point,
+        attempts: Vec<(PipeTile, LoopError)>
+    },
+    MultipleLoops(Vec<PipeTile>)
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Grid(err) => write!(f, "{err}"),
+            LoadError::UnknownTile { at, tile } => write!(f, "Unknown tile '{tile}' at {at}"),
+            LoadError::NoStart => write!(f, "Pipes should have start tile"),
+            LoadError::NoLoop { start, attempts } if attempts.is_empty() => {
+                write!(f, "No pipes connect to start tile at {start}")
+            },
+            LoadError::NoLoop { start, attempts } => {
+                write!(f, "No closed loop passes through start tile at {start}")?;
+                for (pipe, err) in attempts {
+                    write!(f, "\n  as {pipe:?}: {err}")?;
+                }
+                Ok(())
+            },
+            LoadError::MultipleLoops(tiles) => {
+                write!(f, "Start tile could be any of {tiles:?}")
+            }
+        }
+    }
+}
+
+/// Reasons that walking the loop doesn't return to the start tile
+#[derive(Debug, PartialEq)]
+pub enum LoopError {
+    /// Walked off the edge of the area from the tile at the given point
+    OffGrid {
+        from: Point,
+        direction: Cardinal
+    },
+    /// Entered the tile at the given point, but it has no pipe connecting back
+    NoConnection {
+        at: Point,
+        direction: Cardinal
+    }
+}
+
+impl fmt::Display for LoopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopError::OffGrid { from, direction } => {
+                write!(f, "walked off the grid going {direction:?} from {from}")
+            },
+            LoopError::NoConnection { at, direction } => {
+                write!(f, "entered tile at {at} going {direction:?} with no matching connection")
+            }
+        }
+    }
+}
+
+impl From<GridError> for LoadError {
+    fn from(err: GridError) -> Self {
+        LoadError::Grid(err)
+    }
+}
+
+impl PipeArea {
+    pub fn load(data: &str) -> Result<Self, LoadError> {
+        let tiles = Grid::parse(data, PipeTile::try_from)?;
+
+        if let Some((i, Err(tile))) = tiles.iter().enumerate().find(|(_, t)| t.is_err()) {
+            return Err(LoadError::UnknownTile { at: tiles.index_to_point(i), tile: *tile });
+        }
+
+        let pipes = tiles.map(|t| t.unwrap_or(PipeTile::Ground));
+
+        let start = pipes.iter()
+            .position(|pipe| *pipe == PipeTile::Start)
+            .ok_or(LoadError::NoStart)?;
+
+        let mut area = Self { pipes, start };
+
+        area.pipes[start] = area.resolve_start()?;
+
+        Ok(area)
+    }
+
+    /// Find the pipe under the start tile by trying each pipe that connects
+    /// to the neighbouring tiles, and keeping the one that forms a closed loop
+    fn resolve_start(&mut self) -> Result<PipeTile, LoadError> {
+        let candidates: Vec<_> = PipeTile::pipes()
+            .filter(|pipe| {
+                let (dir1, dir2) = pipe.directions().unwrap();
+                self.has_connection(self.start, &dir1) &&
+                    self.has_connection(self.start, &dir2)
+            })
+            .collect();
+
+        let mut loops = vec![];
+        let mut attempts = vec![];
+
+        for pipe in candidates {
+            self.pipes[self.start] = pipe;
+            match self.walk_loop() {
+                Ok(_) => loops.push(pipe),
+                Err(err) => attempts.push((pipe, err))
+            }
+        }
+
+        self.pipes[self.start] = PipeTile::Start;
+
+        match loops[..] {
+            [pipe] => Ok(pipe),
+            [] => Err(LoadError::NoLoop {
+                start: self.pipes.index_to_point(self.start),
+                attempts
+            }),
+            _ => Err(LoadError::MultipleLoops(loops))
+        }
+    }
+
+    /// Walks the loop from the start tile, ending with the start tile
+    fn iter(&self) -> PipeAreaIterator<'_> {
+        PipeAreaIterator {
+            area: self,
+            index: self.start,
+            direction: self.pipes[self.start].directions().map(|(dir, _)| dir)
+        }
+    }
+
+    /// Breadth first search from the start tile, following pipe connections
+    /// in both directions around the loop
+    pub fn distance_map(&self) -> DistanceMap {
+        let mut distances = self.pipes.map(|_| None);
+        let mut queue = VecDeque::from([(self.start, 0)]);
+
+        distances[self.start] = Some(0);
+
+        while let Some((index, distance)) = queue.pop_front() {
+            let Some((dir1, dir2)) = self.pipes[index].directions() else {
+                continue;
+            };
+
+            for dir in [dir1, dir2] {
+                if !self.has_connection(index, &dir) {
+                    continue;
+                }
+
+                let next = self.next_index(index, &dir).unwrap();
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        DistanceMap { distances }
+    }
+
+    /// Returns the neighbouring tiles with pipes that connect to the start
+    /// tile, but aren't part of the loop
+    pub fn dead_end_branches(&self) -> Vec<(Cardinal, Point)> {
+        Cardinal::into_iter()
+            .filter(|dir| !self.pipes[self.start].has_direction(dir))
+            .filter(|dir| self.has_connection(self.start, dir))
+            .map(|dir| {
+                let index = self.next_index(self.start, &dir).unwrap();
+                (dir, self.pipes.index_to_point(index))
+            })
+            .collect()
+    }
+
+    /// Returns the tile indices of the loop in walking order, starting with
+    /// the start tile
+    fn loop_indices(&self) -> Vec<usize> {
+        self.walk_loop()
+            .expect("loaded area should have closed loop")
+    }
+
+    /// Walks the loop and returns the tile indices in walking order, or the
+    /// reason the walk didn't make it back to the start tile
+    fn walk_loop(&self) -> Result<Vec<usize>, LoopError> {
+        let mut iter = self.iter();
+        let mut indices = vec![iter.index];
+
+        while let Some(pipe) = iter.next() {
+            pipe?;
+
+            if iter.index == self.start {
+                break;
+            }
+
+            indices.push(iter.index);
+        }
+
+        Ok(indices)
+    }
+
+    /// Count tiles enclosed by the loop using the shoelace formula for the
+    /// area of the loop polygon and Pick's theorem, `A = i + b/2 - 1`, to
+    /// get the number of interior points from the area and loop length
+    pub fn count_enclosed_tiles(&self) -> usize {
+        let points: Vec<_> = self.loop_indices().into_iter()
+            .map(|i| self.pipes.index_to_point(i))
+            .collect();
+
+        let double_area: i64 = points.iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+            .sum();
+
+        // the shoelace sum is negative or positive depending on which way
+        // round the loop is walked, only its size is the area
+        let double_area = double_area.abs();
+
+        ((double_area - points.len() as i64) / 2 + 1) as usize
+    }
+
+    /// Count tiles enclosed by the loop by scanning each row and toggling
+    /// inside/outside each time a loop tile with a north connection is crossed
+    pub fn count_enclosed_tiles_scanline(&self) -> usize {
+        self.classify_tiles().iter()
+            .filter(|t| **t == TileClass::Inside)
+            .count()
+    }
+
+    /// Classify every tile as part of the loop, or inside/outside of the loop
+    fn classify_tiles(&self) -> Grid<TileClass> {
+        let mut classes = self.pipes.map(|_| TileClass::Outside);
+        for index in self.loop_indices() {
+            classes[index] = TileClass::Loop;
+        }
+
+        let rows = self.pipes.rows_iter()
+            .zip(classes.rows_iter_mut());
+
+        for (pipes, classes) in rows {
+            let mut inside = false;
+
+            for (pipe, class) in pipes.iter().zip(classes) {
+                if *class == TileClass::Loop {
+                    inside ^= pipe.has_direction(&Cardinal::North);
+                } else if inside {
+                    *class = TileClass::Inside;
+                }
+            }
+        }
+
+        classes
+    }
+
+    /// Returns true if there is a pipe connection from the index in the given direction
+    fn has_connection(&self, from_index: usize, dir: &Cardinal) -> bool {
+        self.next_index(from_index, dir)
+            .map(|i| self.pipes[i].has_direction(&dir.opposite()))
+            .unwrap_or_default()
+    }
+
+    /// Returns next tile index in the given direction, or None if navigating
+    /// in the direction goes out of bounds
+    fn next_index(&self, from_index: usize, dir: &Cardinal) -> Option<usize> {
+        let point = self.pipes.index_to_point(from_index) + dir.offset();
+        self.pipes.point_to_index(point)
+    }
+}
+
+pub struct DistanceMap {
+    distances: Grid<Option<usize>>
+}
+
+impl DistanceMap {
+    /// Returns the number of steps from the start tile, or None if the tile
+    /// can't be reached from the start tile
+    pub fn distance(&self, point: Point) -> Option<usize> {
+        self.distances.get(point).copied().flatten()
+    }
+
+    pub fn max_distance(&self) -> usize {
+        self.distances.iter()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns all tiles that are the furthest distance from the start tile
+    pub fn farthest(&self) -> Vec<Point> {
+        let max = self.max_distance();
+
+        self.distances.iter()
+            .enumerate()
+            .filter(|(_, d)| **d == Some(max))
+            .map(|(i, _)| self.distances.index_to_point(i))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TileClass {
+    Loop,
+    Inside,
+    Outside
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Cardinal {
+    North,
+    East,
+    South,
+    West
+}
+
+impl Cardinal {
+    fn into_iter() -> impl Iterator<Item = Cardinal> {
+        [Cardinal::North, Cardinal::East, Cardinal::South, Cardinal::West].into_iter()
+    }
+
+    fn offset(&self) -> Point {
+        match self {
+            Cardinal::North => Point::new(0, -1),
+            Cardinal::East => Point::new(1, 0),
+            Cardinal::South => Point::new(0, 1),
+            Cardinal::West => Point::new(-1, 0)
+        }
+    }
+
+    fn opposite(&self) -> Self {
+        match self {
+            Cardinal::North => Cardinal::South,
+            Cardinal::East => Cardinal::West,
+            Cardinal::South => Cardinal::North,
+            Cardinal::West => Cardinal::East
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PipeTile {
+    NorthSouth,
+    EastWest,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start
+}
+
+impl TryFrom<char> for PipeTile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '|' => Ok(PipeTile::NorthSouth),
+            '-' => Ok(PipeTile::EastWest),
+            'L' => Ok(PipeTile::NorthEast),
+            'J' => Ok(PipeTile::NorthWest),
+            '7' => Ok(PipeTile::SouthWest),
+            'F' => Ok(PipeTile::SouthEast),
+            '.' => Ok(PipeTile::Ground),
+            'S' => Ok(PipeTile::Start),
+            _ => Err(c)
+        }
+    }
+}
+
+impl PipeTile {
+    fn pipes() -> impl Iterator<Item = PipeTile> {
+        [
+            PipeTile::NorthSouth, PipeTile::EastWest, PipeTile::NorthEast,
+            PipeTile::NorthWest, PipeTile::SouthWest, PipeTile::SouthEast
+        ].into_iter()
+    }
+
+    fn directions(&self) -> Option<(Cardinal, Cardinal)> {
+        match self {
+            PipeTile::NorthSouth => Some((Cardinal::North, Cardinal::South)),
+            PipeTile::EastWest => Some((Cardinal::East, Cardinal::West)),
+            PipeTile::NorthEast => Some((Cardinal::North, Cardinal::East)),
+            PipeTile::NorthWest => Some((Cardinal::North, Cardinal::West)),
+            PipeTile::SouthWest => Some((Cardinal::South, Cardinal::West)),
+            PipeTile::SouthEast => Some((Cardinal::South, Cardinal::East)),
+            _ => None
+        }
+    }
+
+    fn has_direction(&self, d: &Cardinal) -> bool {
+        if let Some((dir1, dir2)) = self.directions() {
+            dir1 == *d || dir2 == *d
+        } else {
+            false
+        }
+    }
+
+    fn out_direction(&self, in_direction: &Cardinal) -> Option<Cardinal> {
+        if let Some((dir1, dir2)) = self.directions() {
+            if in_direction.opposite() == dir1 {
+                Some(dir2)
+            } else {
+                Some(dir1)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+pub struct PipeAreaIterator<'a> {
+    area: &'a PipeArea,
+    index: usize,
+    direction: Option<Cardinal>
+}
+
+impl<'a> Iterator for PipeAreaIterator<'a> {
+    type Item = Result<&'a PipeTile, LoopError>;
+
+    /// Steps to the next tile of the loop, ending after returning to the start
+    /// tile or after the first error
+    fn next(&mut self) -> Option<Self::Item> {
+        let direction = self.direction.take()?;
+
+        let Some(next) = self.area.next_index(self.index, &direction) else {
+            return Some(Err(LoopError::OffGrid {
+                from: self.area.pipes.index_to_point(self.index),
+                direction
+            }));
+        };
+
+        let pipe = &self.area.pipes[next];
+
+        if !pipe.has_direction(&direction.opposite()) {
+            return Some(Err(LoopError::NoConnection {
+                at: self.area.pipes.index_to_point(next),
+                direction
+            }));
+        }
+
+        self.index = next;
+        if next != self.area.start {
+            self.direction = pipe.out_direction(&direction);
+        }
+
+        Some(Ok(pipe))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_enclosed(data: &str, expected: usize) {
+        let area = PipeArea::load(data).unwrap();
+        assert_eq!(area.count_enclosed_tiles(), expected);
+        assert_eq!(area.count_enclosed_tiles_scanline(), expected);
+    }
+
+    #[test]
+    fn enclosed_tiles_square() {
+        assert_enclosed("\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....", 1);
+    }
+
+    #[test]
+    fn enclosed_tiles_none() {
+        assert_enclosed("\
+.S7.
+.LJ.", 0);
+
+        assert_enclosed("\
+F-S
+L-J", 0);
+    }
+
+    #[test]
+    fn enclosed_tiles_squeeze() {
+        // outside reaches between the pipes of the middle column
+        assert_enclosed("\
+S-7.F-7
+|.|.|.|
+|.L-J.|
+|.....|
+L-----J", 9);
+
+        assert_enclosed("\
+F--7F--7.7
+|.F||.FJ..
+|-.LJ.|..F
+S..L|L|J..
+||7.F-J.-.
+|F--JF....
+LJF..F.|..", 14);
+    }
+
+    #[test]
+    fn enclosed_tiles_junk_pipes() {
+        assert_enclosed("\
+--F-7F-7..
+..L7LJ.|..
+..FJF.FJ..
+L-|...L-7.
+F-J...F-J|
+|...FSJ7..
+L---J...-.", 12);
+    }
+
+    #[test]
+    fn resolve_start_tile() {
+        // three pipes connect to start, but only north and south form a loop
+        let area = PipeArea::load("\
+|F-7L...F.
+.|.L-77...
+.||.|||.7.
+FJ|..L7..L
+L7.|..S-J.
+.|....L-7.
+.L------J.").unwrap();
+
+        assert_eq!(area.pipes[area.start], PipeTile::NorthSouth);
+        assert_eq!(area.loop_indices().len(), 28);
+        assert_eq!(area.dead_end_branches(), vec![(Cardinal::East, Point::new(7, 4))]);
+    }
+
+    #[test]
+    fn distance_map() {
+        let area = PipeArea::load("\
+.....
+S-7..
+|.L-7
+L---J").unwrap();
+
+        let distances = area.distance_map();
+
+        assert_eq!(distances.max_distance(), 6);
+        assert_eq!(distances.farthest(), vec![Point::new(4, 3)]);
+        assert_eq!(distances.distance(Point::new(0, 1)), Some(0));
+        assert_eq!(distances.distance(Point::new(3, 2)), Some(4));
+        assert_eq!(distances.distance(Point::new(0, 0)), None);
+        assert!(area.dead_end_branches().is_empty());
+    }
+
+    #[test]
+    fn resolve_start_tile_errors() {
+        assert_eq!(PipeArea::load("F-7\n|.|\nL-J").err(), Some(LoadError::NoStart));
+        assert_eq!(
+            PipeArea::load("S-7\n|x|\nL-J").err(),
+            Some(LoadError::UnknownTile { at: Point::new(1, 1), tile: 'x' })
+        );
+        assert_eq!(
+            PipeArea::load("S-7\n..|\n...").err(),
+            Some(LoadError::NoLoop { start: Point::new(0, 0), attempts: vec![] })
+        );
+
+        let two_loops = "\
+F-7..
+|.|..
+L-S-7
+..|.|
+..L-J";
+
+        assert_eq!(
+            PipeArea::load(two_loops).err(),
+            Some(LoadError::MultipleLoops(vec![PipeTile::NorthWest, PipeTile::SouthEast]))
+        );
+    }
+
+    #[test]
+    fn broken_loop_errors() {
+        let off_grid = LoopError::OffGrid { from: Point::new(0, 2), direction: Cardinal::South };
+        assert_eq!(
+            PipeArea::load("S-7\n|.|\n|-J").err(),
+            Some(LoadError::NoLoop { start: Point::new(0, 0), attempts: vec![(PipeTile::SouthEast, off_grid)] })
+        );
+
+        let no_connection = LoopError::NoConnection { at: Point::new(0, 2), direction: Cardinal::South };
+        assert_eq!(
+            PipeArea::load("S-7\n|.|\n.-J").err(),
+            Some(LoadError::NoLoop { start: Point::new(0, 0), attempts: vec![(PipeTile::SouthEast, no_connection)] })
+        );
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(10, seed, 20).unwrap();
+            let area = PipeArea::load(&generated.input).unwrap();
+
+            assert_eq!(Some(area.distance_map().max_distance().to_string()), generated.part1);
+            assert_eq!(Some(area.count_enclosed_tiles().to_string()), generated.part2);
+        }
+    }
+}
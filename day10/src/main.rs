@@ -1,222 +1,95 @@
 use std::{env, fs, process};
 
-struct PipeArea {
-    pipes: Vec<PipeTile>,
-    cols: usize,
-    rows: usize
-}
-
-impl PipeArea {
-    fn load(data: &str) -> Self {
-        let mut pipes = vec![];
-        let mut cols = None;
-
-        for line in data.lines() {
-            if cols.is_none() {
-                cols = Some(line.len());
-            }
-
-            pipes.extend(line.chars().map(|c| PipeTile::from(c)));
-        }
+use common::{alloc::CountingAlloc, output::{Format, Output}, profile::Profile};
+use day10::{render, PipeArea};
 
-        let rows = pipes.len() / cols.unwrap();
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
 
-        Self {
-            pipes,
-            cols: cols.unwrap(),
-            rows
-        }
-    }
-
-    fn iter(&self) -> PipeAreaIterator {
-        let start_index = self.pipes.iter()
-            .position(|pipe| *pipe == PipeTile::Start)
-            .expect("Pipes should have start tile");
+fn main() {
+    let (mut profile, args) = Profile::from_args(env::args());
+    let (output, mut args) = Output::from_args(10, args);
 
-        // get first direction that has pipe tile next to start
-        let start_direction = Cardinal::into_iter()
-            .find(|dir| self.has_connection(start_index, dir))
-            .unwrap();
+    let cmd_name = args.next().unwrap();
 
-        PipeAreaIterator {
-            area: self,
-            index: start_index,
-            direction: Some(start_direction)
+    let mut run_part2 = false;
+    let mut render = false;
+    let mut ansi = true;
+    let mut farthest = false;
+    let mut ppm_file = None;
+    let mut input_file = None;
+
+    while let Some(a) = args.next() {
+        match a.as_str() {
+            "-p2" => run_part2 = true,
+            "--render" => render = true,
+            "--no-color" => ansi = false,
+            "--farthest" => farthest = true,
+            "--ppm" => ppm_file = Some(args.next().unwrap_or_else(|| print_usage_exit(&cmd_name))),
+            _ => input_file = Some(a)
         }
     }
 
-    fn count_loop_steps(&self) -> usize {
-        self.iter().take_while(|p| **p != PipeTile::Start)
-            .count()
-    }
+    let input_file = input_file.unwrap_or_else(|| print_usage_exit(&cmd_name));
 
-    fn index_to_point(&self, index: usize) -> (i32, i32) {
-        (
-            (index % self.cols) as i32,
-            (index / self.cols) as i32
-        )
+    output.set_input(&input_file);
+    if run_part2 {
+        output.set_part(2);
     }
 
-    fn point_to_index(&self, point: (i32, i32)) -> usize {
-        point.1 as usize * self.cols + point.0 as usize
-    }
-
-    /// Returns true if there is a pipe connection from the index in the given direction
-    fn has_connection(&self, from_index: usize, dir: &Cardinal) -> bool {
-        self.next_index(from_index, dir)
-            .map(|i| self.pipes[i].has_direction(&dir.opposite()))
-            .unwrap_or_default()
-    }
-
-    /// Returns next tile index in the given direction, or None if navigating
-    /// in the direction goes out of bounds
-    fn next_index(&self, from_index: usize, dir: &Cardinal) -> Option<usize> {
-        let limit_x = (self.cols - 1) as i32;
-        let limit_y = (self.rows - 1) as i32;
-
-        let (mut point_x, mut point_y) = self.index_to_point(from_index);
-
-        match dir {
-            Cardinal::North => point_y -= 1,
-            Cardinal::East => point_x += 1,
-            Cardinal::South => point_y += 1,
-            Cardinal::West => point_x -= 1
-        }
-
-        if point_x < 0 || point_x > limit_x || point_y < 0 || point_y > limit_y {
-            None
-        } else {
-            Some(self.point_to_index((point_x, point_y)))
-        }
-    }
-}
+    let file_content = profile.phase("read", || {
+        fs::read_to_string(&input_file).unwrap_or_else(|err| output.fail("read", err))
+    });
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Cardinal {
-    North,
-    East,
-    South,
-    West
-}
+    let area = profile.phase("parse", || PipeArea::load(&file_content))
+        .unwrap_or_else(|err| output.fail("load", err));
 
-impl Cardinal {
-    fn into_iter() -> impl Iterator<Item = Cardinal> {
-        [Cardinal::North, Cardinal::East, Cardinal::South, Cardinal::West].into_iter()
-    }
+    if render {
+        let text = render::render_text(&area, ansi);
 
-    fn opposite(&self) -> Self {
-        match self {
-            Cardinal::North => Cardinal::South,
-            Cardinal::East => Cardinal::West,
-            Cardinal::South => Cardinal::North,
-            Cardinal::West => Cardinal::East
+        // stdout only has the answer object in json format
+        match output.format() {
+            Format::Text => print!("{text}"),
+            Format::Json => eprint!("{text}")
         }
     }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum PipeTile {
-    NorthSouth,
-    EastWest,
-    NorthEast,
-    NorthWest,
-    SouthWest,
-    SouthEast,
-    Ground,
-    Start
-}
 
-impl PipeTile {
-    fn from(c: char) -> Self {
-        match c {
-            '|' => PipeTile::NorthSouth,
-            '-' => PipeTile::EastWest,
-            'L' => PipeTile::NorthEast,
-            'J' => PipeTile::NorthWest,
-            '7' => PipeTile::SouthWest,
-            'F' => PipeTile::SouthEast,
-            '.' => PipeTile::Ground,
-            'S' => PipeTile::Start,
-            _ => panic!("Unexpected char {c}")
-        }
+    if let Some(ppm_file) = ppm_file {
+        fs::File::create(&ppm_file)
+            .and_then(|mut file| render::write_ppm(&area, &mut file))
+            .unwrap_or_else(|err| output.fail("write", format!("{ppm_file}: {err}")));
     }
 
-    fn directions(&self) -> Option<(Cardinal, Cardinal)> {
-        match self {
-            PipeTile::NorthSouth => Some((Cardinal::North, Cardinal::South)),
-            PipeTile::EastWest => Some((Cardinal::East, Cardinal::West)),
-            PipeTile::NorthEast => Some((Cardinal::North, Cardinal::East)),
-            PipeTile::NorthWest => Some((Cardinal::North, Cardinal::West)),
-            PipeTile::SouthWest => Some((Cardinal::South, Cardinal::West)),
-            PipeTile::SouthEast => Some((Cardinal::South, Cardinal::East)),
-            _ => None
-        }
-    }
+    if run_part2 {
+        let count = profile.phase("solve", || area.count_enclosed_tiles());
 
-    fn has_direction(&self, d: &Cardinal) -> bool {
-        if let Some((dir1, dir2)) = self.directions() {
-            dir1 == *d || dir2 == *d
-        } else {
-            false
+        let scanline_count = area.count_enclosed_tiles_scanline();
+        if count != scanline_count {
+            eprintln!("Scanline count {scanline_count} doesn't match {count}");
         }
-    }
 
-    fn out_direction(&self, in_direction: &Cardinal) -> Option<Cardinal> {
-        if let Some((dir1, dir2)) = self.directions() {
-            if in_direction.opposite() == dir1 {
-                Some(dir2)
-            } else {
-                Some(dir1)
-            }
-        } else {
-            None
+        output.answer(count);
+    } else {
+        for (dir, point) in area.dead_end_branches() {
+            eprintln!("Pipe at {point} connects to start tile from {dir:?} but isn't part of the loop");
         }
-    }
-}
-
-struct PipeAreaIterator<'a> {
-    area: &'a PipeArea,
-    index: usize,
-    direction: Option<Cardinal>
-}
 
-impl<'a> Iterator for PipeAreaIterator<'a> {
-    type Item = &'a PipeTile;
+        let distances = profile.phase("solve", || area.distance_map());
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(direction) = self.direction {
-            if let Some(next) = self.area.next_index(self.index, &direction) {
-                self.index = next;
-                self.direction = self.area.pipes[next].out_direction(&direction);
-                return Some(&self.area.pipes[next]);
+        // only the answer is printed as json
+        if farthest && output.format() == Format::Text {
+            for point in distances.farthest() {
+                println!("{point}: {}", distances.distance(point).unwrap());
             }
         }
 
-        None
+        output.answer(distances.max_distance());
     }
-}
-
-fn main() {
-    let mut args = env::args();
 
-    let cmd_name = args.next().unwrap();
-
-    let mut _run_part2 = false;
-    let input_file = match args.next() {
-        Some(a) if a == "-p2" => {
-            _run_part2 = true;
-            args.next()
-        },
-        Some(a) => Some(a),
-        None => None
-    }.unwrap_or_else(|| {
-        println!("{cmd_name} input.txt");
-        process::exit(1)
-    });
-
-    let file_content = fs::read_to_string(input_file)
-        .expect("input file should exist and be text file");
+    profile.report();
+}
 
-    let area = PipeArea::load(&file_content);
-    println!("{:?}", (area.count_loop_steps() + 1) / 2);
+fn print_usage_exit(me: &str) -> ! {
+    println!("{me} [-p2] [--render [--no-color]] [--farthest] [--ppm image.ppm] [--time] [--mem] [--format text|json] input.txt");
+    process::exit(1)
 }
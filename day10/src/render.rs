@@ -0,0 +1,165 @@
+use std::io::{self, Write};
+
+use crate::{Cardinal, PipeArea, PipeTile, TileClass};
+
+const DIM: &str = "\x1b[2m";
+const BOLD: &str = "\x1b[1m";
+const INSIDE: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+const INSIDE_MARK: char = '●';
+const OUTSIDE_MARK: char = '·';
+
+fn box_char(pipe: &PipeTile) -> Option<char> {
+    match pipe {
+        PipeTile::NorthSouth => Some('│'),
+        PipeTile::EastWest => Some('─'),
+        PipeTile::NorthEast => Some('└'),
+        PipeTile::NorthWest => Some('┘'),
+        PipeTile::SouthWest => Some('┐'),
+        PipeTile::SouthEast => Some('┌'),
+        _ => None
+    }
+}
+
+/// Render the area with box drawing characters for the pipes.
+///
+/// Tiles inside the loop are marked with `●`, pipes outside of the loop are
+/// dimmed and ground outside of the loop is marked with `·`. Escape codes for
+/// colour and dimming are only written when `ansi` is true.
+pub fn render_text(area: &PipeArea, ansi: bool) -> String {
+    let classes = area.classify_tiles();
+    let mut out = String::new();
+
+    let style = |out: &mut String, code: &str| if ansi { out.push_str(code) };
+
+    for (i, (pipe, class)) in area.pipes.iter().zip(&classes).enumerate() {
+        match class {
+            TileClass::Loop => {
+                if i == area.start {
+                    style(&mut out, BOLD);
+                }
+                out.push(box_char(pipe).unwrap());
+            },
+            TileClass::Inside => {
+                style(&mut out, INSIDE);
+                out.push(INSIDE_MARK);
+            },
+            TileClass::Outside => {
+                style(&mut out, DIM);
+                out.push(box_char(pipe).unwrap_or(OUTSIDE_MARK));
+            }
+        }
+
+        if *class != TileClass::Loop || i == area.start {
+            style(&mut out, RESET);
+        }
+
+        if (i + 1) % area.pipes.cols() == 0 {
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Pixels per tile in the image, each tile is drawn as a 3x3 block with the
+/// pipe connections drawn from the centre pixel
+const SCALE: usize = 3;
+
+const LOOP_RGB: [u8; 3] = [255, 255, 255];
+const JUNK_RGB: [u8; 3] = [90, 90, 90];
+const INSIDE_RGB: [u8; 3] = [40, 160, 60];
+const OUTSIDE_RGB: [u8; 3] = [0, 0, 0];
+
+/// Write the area as a binary PPM (P6) image. Loop pipes are white, other
+/// pipes are grey and tiles inside the loop are filled green.
+pub fn write_ppm<W: Write>(area: &PipeArea, out: &mut W) -> io::Result<()> {
+    let classes = area.classify_tiles();
+
+    let width = area.pipes.cols() * SCALE;
+    let height = area.pipes.rows() * SCALE;
+    let mut pixels = vec![OUTSIDE_RGB; width * height];
+
+    for (i, (pipe, class)) in area.pipes.iter().zip(&classes).enumerate() {
+        let point = area.pipes.index_to_point(i);
+        let (cx, cy) = (point.x as usize * SCALE + 1, point.y as usize * SCALE + 1);
+
+        let rgb = match class {
+            TileClass::Loop => LOOP_RGB,
+            TileClass::Inside => INSIDE_RGB,
+            TileClass::Outside => OUTSIDE_RGB
+        };
+
+        if *class == TileClass::Inside {
+            for py in cy - 1 ..= cy + 1 {
+                pixels[py * width + cx - 1 ..= py * width + cx + 1].fill(rgb);
+            }
+        }
+
+        if let Some((dir1, dir2)) = pipe.directions() {
+            let rgb = if *class == TileClass::Loop { rgb } else { JUNK_RGB };
+
+            pixels[cy * width + cx] = rgb;
+
+            for dir in [dir1, dir2] {
+                let (px, py) = match dir {
+                    Cardinal::North => (cx, cy - 1),
+                    Cardinal::East => (cx + 1, cy),
+                    Cardinal::South => (cx, cy + 1),
+                    Cardinal::West => (cx - 1, cy)
+                };
+
+                pixels[py * width + px] = rgb;
+            }
+        }
+    }
+
+    write!(out, "P6\n{width} {height}\n255\n")?;
+    out.write_all(&pixels.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: &str = "\
+.F7..
+FS|F7
+|.LJ|
+L---J
+.....";
+
+    #[test]
+    fn render_plain_text() {
+        let area = PipeArea::load(AREA).unwrap();
+
+        assert_eq!(render_text(&area, false), "\
+·┌┐··
+┌┘│┌┐
+│●└┘│
+└───┘
+·····
+");
+    }
+
+    #[test]
+    fn ppm_image() {
+        let area = PipeArea::load(AREA).unwrap();
+
+        let mut image = vec![];
+        write_ppm(&area, &mut image).unwrap();
+
+        let header = b"P6\n15 15\n255\n";
+        assert!(image.starts_with(header));
+        assert_eq!(image.len() - header.len(), 15 * 15 * 3);
+
+        // centre of the start tile, which is on the loop
+        let pixel = header.len() + (4 * 15 + 4) * 3;
+        assert_eq!(image[pixel..pixel + 3], LOOP_RGB);
+
+        // centre of the tile inside the loop
+        let pixel = header.len() + (7 * 15 + 4) * 3;
+        assert_eq!(image[pixel..pixel + 3], INSIDE_RGB);
+    }
+}
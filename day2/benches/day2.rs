@@ -0,0 +1,43 @@
+use std::hint::black_box;
+
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day2::{parse_bag, possible_game_total, power_total, Game};
+
+const SIZES: [usize; 3] = [100, 1000, 10000];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day2/load");
+
+    for (size, input) in sized_inputs(2, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| {
+                black_box(input).lines()
+                    .map(|line| line.parse::<Game>().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Solvers read the games as they go, so these include parsing
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day2/solve");
+    let bag = parse_bag("red=12,green=13,blue=14").unwrap();
+
+    for (size, input) in sized_inputs(2, &SIZES) {
+        group.bench_with_input(BenchmarkId::new("part1", size), &input, |b, input| {
+            b.iter(|| possible_game_total(input.as_bytes(), &bag).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &input, |b, input| {
+            b.iter(|| power_total(input.as_bytes(), &bag).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
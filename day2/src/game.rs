@@ -0,0 +1,177 @@
+use std::{cmp, collections::HashMap, error, fmt, str::FromStr};
+
+/// Count of cubes for each colour name
+pub type CubeSet = HashMap<String, i32>;
+
+/// Handful of cubes shown in a game, in the order they were listed
+pub type Draw = Vec<(String, i32)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub number: i32,
+    pub results: Vec<Draw>
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGameError {
+    MissingPrefix,
+    InvalidNumber(String),
+    InvalidCube(String),
+    InvalidCount(String)
+}
+
+impl fmt::Display for ParseGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGameError::MissingPrefix => write!(f, "line should start with 'Game N:'"),
+            ParseGameError::InvalidNumber(s) => write!(f, "game number '{s}' should be integer"),
+            ParseGameError::InvalidCube(s) => write!(f, "cubes '{s}' should be 'count colour'"),
+            ParseGameError::InvalidCount(s) => write!(f, "cube count '{s}' should be integer")
+        }
+    }
+}
+
+impl error::Error for ParseGameError { }
+
+impl Game {
+    /// Returns true if every set of cubes could be drawn from the bag, colours
+    /// that aren't in the bag have no cubes
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        self.results.iter().all(|set| {
+            set.iter().all(|(colour, count)| {
+                *count <= bag.get(colour).copied().unwrap_or_default()
+            })
+        })
+    }
+
+    pub fn max_cube_set(&self) -> CubeSet {
+        let mut max_set = CubeSet::new();
+
+        for set in &self.results {
+            for (colour, count) in set {
+                let max = max_set.entry(colour.clone()).or_default();
+                *max = cmp::max(*max, *count);
+            }
+        }
+
+        max_set
+    }
+
+    /// Product of the max cube counts for each colour in the bag and each
+    /// colour drawn in the game, colours never drawn have zero cubes
+    pub fn power(&self, bag: &CubeSet) -> i32 {
+        let max_set = self.max_cube_set();
+
+        bag.keys()
+            .chain(max_set.keys().filter(|colour| !bag.contains_key(*colour)))
+            .map(|colour| max_set.get(colour).copied().unwrap_or_default())
+            .product()
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseGameError;
+
+    /// Parse a line in the form `Game 1: 3 blue, 4 red; 1 red, 2 green`
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (game, cube_sets) = line.split_once(':')
+            .ok_or(ParseGameError::MissingPrefix)?;
+
+        let number = game.strip_prefix("Game ")
+            .ok_or(ParseGameError::MissingPrefix)?;
+
+        let number = number.parse::<i32>()
+            .map_err(|_| ParseGameError::InvalidNumber(number.into()))?;
+
+        let results = cube_sets.trim().split(';')
+            .map(|set| {
+                set.trim().split(',').map(|cubes| {
+                    let (count, colour) = cubes.trim().split_once(' ')
+                        .ok_or_else(|| ParseGameError::InvalidCube(cubes.into()))?;
+
+                    let count = count.parse::<i32>()
+                        .map_err(|_| ParseGameError::InvalidCount(count.into()))?;
+
+                    Ok((colour.to_string(), count))
+                }).collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Game { number, results })
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.number)?;
+
+        for (i, set) in self.results.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+
+            for (j, (colour, count)) in set.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+
+                write!(f, "{count} {colour}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse bag limits in the form `red=12,green=13,blue=14`, commas or
+/// whitespace separate colours
+pub fn parse_bag(data: &str) -> Option<CubeSet> {
+    data.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (colour, count) = s.split_once('=')?;
+            Some((colour.trim().to_string(), count.trim().parse::<i32>().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_game() {
+        let game: Game = "Game 7: 3 blue, 4 red; 1 red".parse().unwrap();
+
+        assert_eq!(game.number, 7);
+        assert_eq!(game.results, vec![
+            vec![("blue".into(), 3), ("red".into(), 4)],
+            vec![("red".into(), 1)]
+        ]);
+
+        assert_eq!("Game: 1 red".parse::<Game>(), Err(ParseGameError::MissingPrefix));
+        assert_eq!("Game 1: red".parse::<Game>(), Err(ParseGameError::InvalidCube("red".into())));
+        assert_eq!("Game 1: x red".parse::<Game>(), Err(ParseGameError::InvalidCount("x".into())));
+    }
+
+    fn game_strategy() -> impl Strategy<Value = Game> {
+        let draw = prop::collection::vec(("[a-z]{1,8}", 0..100i32), 1..5);
+        let results = prop::collection::vec(draw, 1..7);
+
+        (1..1000i32, results)
+            .prop_map(|(number, results)| Game { number, results })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(game in game_strategy()) {
+            let line = game.to_string();
+            let parsed: Game = line.parse().unwrap();
+
+            prop_assert_eq!(&parsed, &game);
+            prop_assert_eq!(parsed.to_string(), line);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Reading and writing cube game logs
+
+use std::io::BufRead;
+
+use common::input::{self, LineError};
+
+pub mod game;
+pub mod stats;
+
+pub use game::{parse_bag, CubeSet, Draw, Game, ParseGameError};
+
+/// Read games from the input one line at a time
+pub fn read_games<R: BufRead>(input: R) -> impl Iterator<Item = Result<Game, LineError<ParseGameError>>> {
+    input::lines(input)
+        .map(|line| line?.parse::<Game>().map_err(LineError::Parse))
+}
+
+/// Sum of the numbers of the games that are possible with the bag
+pub fn possible_game_total<R: BufRead>(input: R, bag: &CubeSet) -> Result<i32, LineError<ParseGameError>> {
+    let mut total = 0;
+
+    for game in read_games(input) {
+        let game = game?;
+        if game.is_possible(bag) {
+            total += game.number;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Sum of the power of the minimal set of cubes for each game
+pub fn power_total<R: BufRead>(input: R, bag: &CubeSet) -> Result<i32, LineError<ParseGameError>> {
+    read_games(input)
+        .map(|game| game.map(|game| game.power(bag)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totals_report_bad_lines() {
+        let bag = parse_bag("red=2,blue=2").unwrap();
+
+        assert_eq!(possible_game_total("Game 1: 1 red\nGame 2: 3 red\n".as_bytes(), &bag).ok(), Some(1));
+        assert_eq!(power_total("Game 1: 1 red, 2 blue\n".as_bytes(), &bag).ok(), Some(2));
+        assert!(matches!(
+            possible_game_total("Game 1: 1 red\nGame 2 3 red\n".as_bytes(), &bag),
+            Err(LineError::Parse(ParseGameError::MissingPrefix))
+        ));
+        assert!(matches!(
+            power_total("Game 1: x red\n".as_bytes(), &bag),
+            Err(LineError::Parse(ParseGameError::InvalidCount(count))) if count == "x"
+        ));
+        assert!(matches!(
+            power_total(&b"Game 1: 1 red\nGame 2: \xff red\n"[..], &bag),
+            Err(LineError::Read(_))
+        ));
+    }
+
+    #[test]
+    fn generated_answers() {
+        let bag = parse_bag("red=12,green=13,blue=14").unwrap();
+
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(2, seed, 20).unwrap();
+            let input = generated.input.as_bytes();
+
+            assert_eq!(possible_game_total(input, &bag).ok().map(|t| t.to_string()), generated.part1);
+            assert_eq!(power_total(input, &bag).ok().map(|t| t.to_string()), generated.part2);
+        }
+    }
+}
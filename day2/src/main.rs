@@ -1,124 +1,109 @@
-use std::{cmp, collections::HashMap, env, fs::read_to_string, process, str::FromStr};
+use std::{env, fs::read_to_string, process};
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-enum CubeColor {
-    Red,
-    Green,
-    Blue
-}
+use common::{alloc::CountingAlloc, input, output::Output, profile::Profile};
+use day2::{parse_bag, stats, CubeSet, Game};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
 
-impl FromStr for CubeColor {
-    type Err = ();
+const DEFAULT_BAG: &str = "red=12,green=13,blue=14";
+
+fn main() {
+    let (mut profile, args) = Profile::from_args(env::args());
+    let (output, mut args) = Output::from_args(2, args);
+
+    // first arg is command name
+    let cmd_name = args.next().unwrap();
 
-    fn from_str(input: &str) -> Result<CubeColor, Self::Err> {
-        match input {
-            "red" => Ok(CubeColor::Red),
-            "green" => Ok(CubeColor::Green),
-            "blue" => Ok(CubeColor::Blue),
-            _ => Err(())
+    let mut run_part2 = false;
+    let mut show_stats = false;
+    let mut bag = None;
+    let mut input_file = None;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(&cmd_name));
+
+        match a.as_str() {
+            "-p2" => run_part2 = true,
+            "--stats" => show_stats = true,
+            "--bag" => bag = Some(value()),
+            "--bag-file" => {
+                let bag_file = value();
+                let data = read_to_string(&bag_file)
+                    .unwrap_or_else(|err| output.fail("read", format!("{bag_file}: {err}")));
+                bag = Some(data);
+            },
+            _ => input_file = Some(a)
         }
     }
-}
 
-#[derive(Debug)]
-struct Game {
-    number: i32,
-    results: Vec<HashMap<CubeColor, i32>>
-}
+    let input_file = input_file.unwrap_or_else(|| print_usage_exit(&cmd_name));
 
-impl Game {
-    fn is_possible(&self, rgb: (i32, i32, i32)) -> bool {
-        self.results.iter().all(|set| {
-            set.get(&CubeColor::Red).map_or(true, |v| *v <= rgb.0) &&
-            set.get(&CubeColor::Green).map_or(true, |v| *v <= rgb.1) &&
-            set.get(&CubeColor::Blue).map_or(true, |v| *v <= rgb.2)
-        })
+    output.set_input(&input_file);
+    if run_part2 {
+        output.set_part(2);
     }
 
-    fn max_cube_set(&self) -> (i32, i32, i32) {
-        let (mut max_red, mut max_green, mut max_blue) = (0, 0, 0);
+    let bag = parse_bag(bag.as_deref().unwrap_or(DEFAULT_BAG))
+        .unwrap_or_else(|| print_usage_exit(&cmd_name));
 
-        for set in &self.results {
-            if let Some(red) = set.get(&CubeColor::Red) {
-                max_red = cmp::max(max_red, *red);
-            }
+    let input = profile.phase("read", || input::try_open(&input_file))
+        .unwrap_or_else(|err| output.fail("read", err));
 
-            if let Some(green) = set.get(&CubeColor::Green) {
-                max_green = cmp::max(max_green, *green);
-            }
+    if show_stats {
+        let games: Vec<Game> = day2::read_games(input)
+            .collect::<Result<_, _>>()
+            .unwrap_or_else(|err| output.fail(err.kind(), err));
 
-            if let Some(blue) = set.get(&CubeColor::Blue) {
-                max_blue = cmp::max(max_blue, *blue);
-            }
-        }
-
-        (max_red, max_green, max_blue)
+        print_stats(&games, &bag);
+        return;
     }
+
+    // games are parsed as they are read, so solving includes reading and parsing
+    let total = profile.phase("solve", || if run_part2 {
+        day2::power_total(input, &bag)
+    } else {
+        day2::possible_game_total(input, &bag)
+    }).unwrap_or_else(|err| output.fail(err.kind(), err));
+
+    output.answer(total);
+    profile.report();
 }
 
-fn main() {
-    let mut args = env::args();
+fn print_stats(games: &[Game], bag: &CubeSet) {
+    let mut minimal_bag: Vec<_> = stats::minimal_bag(games).into_iter().collect();
+    minimal_bag.sort();
 
-    // first arg is command name
-    let cmd_name = args.next().unwrap();
+    let minimal_bag: Vec<_> = minimal_bag.iter()
+        .map(|(colour, count)| format!("{colour}={count}"))
+        .collect();
 
-    let mut run_part2 = false;
-    let input_file = match args.next() {
-        Some(a) if a == "-p2" => {
-            run_part2 = true;
-            args.next()
-        },
-        Some(a) => Some(a),
-        None => None
-    }.unwrap_or_else(|| {
-        println!("{cmd_name} [input.txt]");
-        process::exit(1);
-    });
-
-    let mut total = 0;
-
-    for line in read_to_string(input_file).expect("input").lines() {
-        let mut line_parts = line.splitn(2, ':');
-        let (mut game, cube_sets) = (
-            line_parts.next().expect("game prefix").splitn(2, ' '),
-            line_parts.next().expect("cube sets").trim().split(';')
-        );
-
-        let game_number = game.nth(1)
-            .expect("game number")
-            .parse::<i32>()
-            .expect("game number integer");
-
-        let game = Game {
-            number: game_number,
-            results: cube_sets.map(|set| {
-                set.trim().split(',').map(|x| {
-                    let mut cube_parts = x.trim().splitn(2, ' ');
-
-                    let cube_count = cube_parts.next()
-                        .expect("cube count")
-                        .parse::<i32>()
-                        .expect("cube count integer");
-
-                    let cube_colour = cube_parts.next()
-                        .expect("cube colour")
-                        .parse::<CubeColor>()
-                        .expect("cube colour enum");
-
-                    ( cube_colour, cube_count )
-                }).collect()
-            }).collect()
-        };
-
-        if run_part2 {
-            let max_set = game.max_cube_set();
-            total += max_set.0 * max_set.1 * max_set.2;
-        } else {
-            if game.is_possible((12, 13, 14)) {
-                total += game.number;
-            }
+    println!("Minimal bag: {}", minimal_bag.join(","));
+
+    for (colour, histogram) in stats::histograms(games) {
+        println!();
+        println!("Draws of {colour}");
+
+        for (count, draws) in histogram {
+            println!("{count:>4} {draws:>5} {}", "#".repeat(draws));
         }
     }
 
-    println!("{total}");
+    println!();
+    println!("Log likelihood per game");
+
+    let mut total = 0.0;
+    for game in games {
+        let likelihood = game.log_likelihood(bag);
+        total += likelihood;
+
+        println!("{:>4} {likelihood:>12.4}", game.number);
+    }
+
+    println!("Total {total:.4}");
+}
+
+fn print_usage_exit(me: &str) -> ! {
+    println!("{me} [-p2] [--stats] [--bag red=12,green=13,blue=14 | --bag-file bag.txt] [--time] [--mem] [--format text|json] [input.txt]");
+    process::exit(1)
 }
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use crate::{CubeSet, Draw, Game};
+
+/// Smallest bag that every game could have been played with, the max count
+/// of each colour over all games
+pub fn minimal_bag(games: &[Game]) -> CubeSet {
+    let mut bag = CubeSet::new();
+
+    for game in games {
+        for (colour, count) in game.max_cube_set() {
+            let max = bag.entry(colour).or_default();
+            *max = (*max).max(count);
+        }
+    }
+
+    bag
+}
+
+/// For each colour, the number of draws that showed each count of cubes
+pub fn histograms(games: &[Game]) -> BTreeMap<String, BTreeMap<i32, usize>> {
+    let mut histograms: BTreeMap<String, BTreeMap<i32, usize>> = BTreeMap::new();
+
+    for (colour, count) in games.iter().flat_map(|g| g.results.iter().flatten()) {
+        *histograms.entry(colour.clone())
+            .or_default()
+            .entry(*count)
+            .or_default() += 1;
+    }
+
+    histograms
+}
+
+/// Natural log of `n choose k`
+fn ln_choose(n: i32, k: i32) -> f64 {
+    if k < 0 || k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    (1..=k).map(|i| ((n - k + i) as f64).ln() - (i as f64).ln())
+        .sum()
+}
+
+/// Log likelihood of drawing the handful of cubes from the bag without
+/// replacement. Colours in the bag that aren't listed weren't drawn.
+pub fn draw_log_likelihood(draw: &Draw, bag: &CubeSet) -> f64 {
+    let bag_total: i32 = bag.values().sum();
+    let draw_total: i32 = draw.iter().map(|(_, count)| count).sum();
+
+    // an impossible draw can make both sides of the ratio -inf, giving NaN
+    let too_many = draw.iter()
+        .any(|(colour, count)| *count > bag.get(colour).copied().unwrap_or_default());
+    if too_many || draw_total > bag_total {
+        return f64::NEG_INFINITY;
+    }
+
+    let ways: f64 = draw.iter()
+        .map(|(colour, count)| ln_choose(bag.get(colour).copied().unwrap_or_default(), *count))
+        .sum();
+
+    ways - ln_choose(bag_total, draw_total)
+}
+
+impl Game {
+    /// Log likelihood of all the draws of the game, the cubes are put back in
+    /// the bag between draws
+    pub fn log_likelihood(&self, bag: &CubeSet) -> f64 {
+        self.results.iter()
+            .map(|draw| draw_log_likelihood(draw, bag))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bag(cubes: &[(&str, i32)]) -> CubeSet {
+        cubes.iter().map(|(c, n)| (c.to_string(), *n)).collect()
+    }
+
+    #[test]
+    fn likelihood() {
+        let bag = bag(&[("red", 2), ("blue", 2)]);
+
+        // 2 choose 1 * 2 choose 1 / 4 choose 2
+        let draw = vec![("red".to_string(), 1), ("blue".to_string(), 1)];
+        assert!((draw_log_likelihood(&draw, &bag) - (4.0f64 / 6.0).ln()).abs() < 1e-9);
+
+        let draw = vec![("red".to_string(), 3)];
+        assert_eq!(draw_log_likelihood(&draw, &bag), f64::NEG_INFINITY);
+
+        let game: Game = "Game 1: 2 red; 1 blue".parse().unwrap();
+        let expected = (1.0f64 / 6.0).ln() + (2.0f64 / 4.0).ln();
+        assert!((game.log_likelihood(&bag) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn impossible_draw() {
+        let bag = bag(&[("red", 12), ("green", 13), ("blue", 14)]);
+
+        let draw = vec![("red".to_string(), 15), ("green".to_string(), 14), ("blue".to_string(), 12)];
+        assert_eq!(draw_log_likelihood(&draw, &bag), f64::NEG_INFINITY);
+
+        let game: Game = "Game 1: 15 red, 14 green, 12 blue; 1 red".parse().unwrap();
+        assert_eq!(game.log_likelihood(&bag), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn minimal_bag_and_histograms() {
+        let games: Vec<Game> = ["Game 1: 2 red; 1 blue", "Game 2: 1 red, 3 blue"].iter()
+            .map(|l| l.parse().unwrap())
+            .collect();
+
+        assert_eq!(minimal_bag(&games), bag(&[("red", 2), ("blue", 3)]));
+
+        let histograms = histograms(&games);
+        assert_eq!(histograms["red"], BTreeMap::from([(1, 1), (2, 1)]));
+        assert_eq!(histograms["blue"], BTreeMap::from([(1, 1), (3, 1)]));
+    }
+}
@@ -0,0 +1,41 @@
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day3::{Board, Options};
+
+const SIZES: [usize; 3] = [25, 400, 2500];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day3/load");
+
+    for (size, input) in sized_inputs(3, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter_batched(
+                || input.clone(),
+                |input| Board::load(input, Options::default()).unwrap(),
+                BatchSize::SmallInput
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day3/solve");
+
+    for (size, input) in sized_inputs(3, &SIZES) {
+        let board = Board::load(input, Options::default()).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("part1", size), &board, |b, board| {
+            b.iter(|| board.part_number_total())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &board, |b, board| {
+            b.iter(|| board.gear_ratio_total())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
@@ -0,0 +1,304 @@
+//! Part numbers and gears in an engine schematic
+
+use std::{collections::BTreeSet, error, fmt, ops::Range};
+
+use common::grid::{Grid, GridError, Point};
+
+mod overlay;
+pub mod report;
+
+enum Cell {
+    Space,
+    Number { content: char },
+    Symbol { content: char }
+}
+
+/// Run of digits on a single row of the schematic
+struct NumberSpan {
+    id: usize,
+    value: u32,
+    row: usize,
+    cols: Range<usize>
+}
+
+pub struct Board {
+    cells: Grid<Cell>,
+    spans: Vec<NumberSpan>,
+    /// Id of the number span that covers each cell
+    span_ids: Grid<Option<usize>>,
+    options: Options
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Adjacency {
+    /// Only cells above, below, left or right
+    Orthogonal,
+    /// Orthogonal cells and the four diagonal cells
+    Diagonal
+}
+
+/// Options for running variants of the schematic rules
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Chars that count as symbols, or None for any char that isn't a digit or `.`
+    pub symbols: Option<String>,
+    pub gear: char,
+    /// Number of part numbers a gear must be adjacent to
+    pub gear_neighbours: usize,
+    pub adjacency: Adjacency
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    Grid(GridError),
+    /// Number starting at the point doesn't fit in a u32
+    NumberTooLarge(Point)
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Grid(err) => write!(f, "Schematic should be rectangular: {err}"),
+            LoadError::NumberTooLarge(at) => write!(f, "Number at {at} is too large")
+        }
+    }
+}
+
+impl error::Error for LoadError { }
+
+impl From<GridError> for LoadError {
+    fn from(err: GridError) -> Self {
+        LoadError::Grid(err)
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            symbols: None,
+            gear: '*',
+            gear_neighbours: 2,
+            adjacency: Adjacency::Diagonal
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(c: char) -> Self {
+        if c == '.' {
+            Cell::Space
+        } else if c.is_ascii_digit() {
+            Cell::Number { content: c }
+        } else {
+            Cell::Symbol { content: c }
+        }
+    }
+}
+
+impl Cell {
+    /// Same as `Cell::from`, except symbols not in the options are spaces
+    fn parse(c: char, options: &Options) -> Self {
+        match (Cell::from(c), &options.symbols) {
+            (Cell::Symbol { content }, Some(symbols)) if !symbols.contains(content) => {
+                Cell::Space
+            },
+            (cell, _) => cell
+        }
+    }
+}
+
+impl Board {
+    pub fn load(data: String, options: Options) -> Result<Self, LoadError> {
+        let cells = Grid::parse(&data, |c| Cell::parse(c, &options))?;
+
+        let mut spans: Vec<NumberSpan> = vec![];
+        let mut span_ids = cells.map(|_| None);
+
+        for (row, row_cells) in cells.rows_iter().enumerate() {
+            let mut digits = String::new();
+
+            // extra space at the end of the row finishes a number at the edge
+            for (col, cell) in row_cells.iter().chain([&Cell::Space]).enumerate() {
+                if let Cell::Number { content } = cell {
+                    digits.push(*content);
+                    continue;
+                }
+
+                if digits.is_empty() {
+                    continue;
+                }
+
+                let cols = (col - digits.len())..col;
+                let value = digits.parse::<u32>().map_err(|_| {
+                    LoadError::NumberTooLarge(Point::new(cols.start as i32, row as i32))
+                })?;
+
+                let span = NumberSpan { id: spans.len(), value, row, cols };
+
+                for col in span.cols.clone() {
+                    span_ids[Point::new(col as i32, span.row as i32)] = Some(span.id);
+                }
+
+                spans.push(span);
+                digits.clear();
+            }
+        }
+
+        Ok(Board { cells, spans, span_ids, options })
+    }
+
+    /// Sum of the part numbers, as a u64 since the numbers can be as large
+    /// as a u32
+    pub fn part_number_total(&self) -> u64 {
+        let mut part_ids = BTreeSet::new();
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            if let Cell::Symbol { .. } = cell {
+                part_ids.extend(self.adjacent_spans(i));
+            }
+        }
+
+        part_ids.iter()
+            .map(|id| u64::from(self.spans[*id].value))
+            .sum()
+    }
+
+    /// Sum of the gear ratios, or None if it doesn't fit in a u64, which
+    /// can happen when gears need more than two neighbours
+    pub fn gear_ratio_total(&self) -> Option<u64> {
+        let mut total = 0u64;
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            if self.is_gear_symbol(cell) {
+                let spans = self.adjacent_spans(i);
+                if spans.len() == self.options.gear_neighbours {
+                    total = total.checked_add(self.gear_ratio(&spans)?)?;
+                }
+            }
+        }
+
+        Some(total)
+    }
+
+    /// Product of the numbers in the spans, or None if it doesn't fit in a u64
+    fn gear_ratio(&self, spans: &BTreeSet<usize>) -> Option<u64> {
+        spans.iter()
+            .try_fold(1u64, |ratio, id| ratio.checked_mul(self.spans[*id].value.into()))
+    }
+
+    fn is_gear_symbol(&self, cell: &Cell) -> bool {
+        matches!(cell, Cell::Symbol { content } if *content == self.options.gear)
+    }
+
+    /// Ids of the number spans next to the cell, a number that touches the
+    /// cell more than once is only included once
+    fn adjacent_spans(&self, index: usize) -> BTreeSet<usize> {
+        let point = self.cells.index_to_point(index);
+
+        let neighbours: Box<dyn Iterator<Item = Point>> = match self.options.adjacency {
+            Adjacency::Orthogonal => Box::new(self.cells.neighbours4(point)),
+            Adjacency::Diagonal => Box::new(self.cells.neighbours8(point))
+        };
+
+        neighbours
+            .filter_map(|p| self.span_ids[p])
+            .collect()
+    }
+
+    #[cfg(test)]
+    fn number_at_index(&self, index: usize) -> Option<u32> {
+        self.span_ids[index].map(|id| self.spans[id].value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_at_index() {
+        let board = Board::load("467..114..".into(), Options::default()).unwrap();
+
+        assert_eq!(board.number_at_index(1), Some(467));
+        assert_eq!(board.number_at_index(4), None);
+        assert_eq!(board.number_at_index(6), Some(114));
+    }
+
+    #[test]
+    fn number_spans() {
+        let board = Board::load("12.\n.34".into(), Options::default()).unwrap();
+
+        assert_eq!(board.spans.len(), 2);
+        assert_eq!((board.spans[0].row, board.spans[0].cols.clone()), (0, 0..2));
+        assert_eq!((board.spans[1].row, board.spans[1].cols.clone()), (1, 1..3));
+        assert_eq!(board.number_at_index(2), None);
+    }
+
+    #[test]
+    fn adjacent_by_span() {
+        // same number next to two symbols is only counted once
+        assert_eq!(Board::load("#12$".into(), Options::default()).unwrap().part_number_total(), 12);
+
+        // two different numbers with the same value are both counted
+        let board = Board::load("5.5\n.*.".into(), Options::default()).unwrap();
+        assert_eq!(board.part_number_total(), 10);
+        assert_eq!(board.gear_ratio_total(), Some(25));
+    }
+
+    #[test]
+    fn load_errors() {
+        assert_eq!(
+            Board::load("1..\n..".into(), Options::default()).err(),
+            Some(LoadError::Grid(GridError::RaggedLine { line: 2, expected: 3, found: 2 }))
+        );
+        assert_eq!(
+            Board::load("..99999999999".into(), Options::default()).err(),
+            Some(LoadError::NumberTooLarge(Point::new(2, 0)))
+        );
+    }
+
+    #[test]
+    fn options() {
+        let data = "5.5\n.*.\n#.1";
+
+        let orthogonal = Options { adjacency: Adjacency::Orthogonal, ..Options::default() };
+        assert_eq!(Board::load(data.into(), orthogonal).unwrap().part_number_total(), 0);
+
+        let only_hash = Options { symbols: Some("#".into()), ..Options::default() };
+        assert_eq!(Board::load(data.into(), only_hash).unwrap().part_number_total(), 0);
+
+        let three_way = Options { gear_neighbours: 3, ..Options::default() };
+        assert_eq!(Board::load(data.into(), three_way).unwrap().gear_ratio_total(), Some(25));
+    }
+
+    #[test]
+    fn large_part_numbers() {
+        let board = Board::load("4294967295*4294967295".into(), Options::default()).unwrap();
+        assert_eq!(board.part_number_total(), 8589934590);
+    }
+
+    #[test]
+    fn large_gear_ratios() {
+        let data = "4294967295.4294967295\n..........*..........\n.........4294967295..";
+
+        let board = Board::load(data.into(), Options::default()).unwrap();
+        assert_eq!(board.gear_ratio_total(), Some(0));
+
+        let two_way = Board::load(data.replace(".4294967295..", "............."), Options::default()).unwrap();
+        assert_eq!(two_way.gear_ratio_total(), Some(18446744065119617025));
+
+        let three_way = Options { gear_neighbours: 3, ..Options::default() };
+        assert_eq!(Board::load(data.into(), three_way).unwrap().gear_ratio_total(), None);
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(3, seed, 20).unwrap();
+            let board = Board::load(generated.input, Options::default()).unwrap();
+
+            assert_eq!(Some(board.part_number_total().to_string()), generated.part1);
+            assert_eq!(board.gear_ratio_total().map(|t| t.to_string()), generated.part2);
+        }
+    }
+}
@@ -1,204 +1,107 @@
-use std::{collections::HashSet, env, fs::read_to_string, process};
+use std::{env, fs::read_to_string, process};
 
-enum Cell {
-    Space,
-    Number { content: char },
-    Symbol { content: char }
-}
-
-struct Board {
-    cells: Vec<Cell>,
-    cols: usize,
-    rows: usize
-}
+use common::{alloc::CountingAlloc, output::{Format, Output}, profile::Profile};
+use day3::{Adjacency, Board, Options};
 
-struct Point {
-    x: i32,
-    y: i32
-}
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
 
-impl From<char> for Cell {
-    fn from(c: char) -> Self {
-        if c == '.' {
-            Cell::Space
-        } else if c.is_digit(10) {
-            Cell::Number { content: c }
-        } else {
-            Cell::Symbol { content: c }
-        }
-    }
-}
-
-impl Board {
-    fn load(data: String) -> Self {
-        let mut rows: usize = 0;
-        let mut cols: Option<usize> = None;
-        let mut cells: Vec<Cell> = vec![];
-
-        for line in data.lines() {
-            if cols.is_none() {
-                cols = Some(line.len());
-            }
-
-            for c in line.chars() {
-                cells.push(Cell::from(c));
-            }
-
-            rows += 1;
-        }
-
-        Board {
-            cols: cols.unwrap(),
-            rows,
-            cells
-        }
-    }
+fn main() {
+    let (mut profile, args) = Profile::from_args(env::args());
+    let (output, mut args) = Output::from_args(3, args);
 
-    fn part_number_total(&self) -> u32 {
-        let mut total = 0;
+    // first arg is command name
+    let cmd_name = args.next().unwrap();
 
-        for (i, cell) in self.cells.iter().enumerate() {
-            if let Cell::Symbol { .. } = cell {
-                let numbers = self.adjacent_numbers(i);
-                total += numbers.iter().sum::<u32>();
-            }
+    let mut run_part2 = false;
+    let mut report_format = None;
+    let mut overlay = false;
+    let mut ansi = true;
+    let mut options = Options::default();
+    let mut input_file = None;
+
+    while let Some(a) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| print_usage_exit(&cmd_name));
+
+        match a.as_str() {
+            "-p2" => run_part2 = true,
+            "--report" => report_format = Some(value()),
+            "--overlay" => overlay = true,
+            "--no-color" => ansi = false,
+            "--symbols" => options.symbols = Some(value()),
+            "--gear" => {
+                options.gear = value().chars().next()
+                    .unwrap_or_else(|| print_usage_exit(&cmd_name));
+            },
+            "--gear-neighbours" => {
+                // a gear needs at least one part number to have a ratio
+                options.gear_neighbours = value().parse::<usize>().ok()
+                    .filter(|n| *n > 0)
+                    .unwrap_or_else(|| print_usage_exit(&cmd_name));
+            },
+            "--adjacency" => {
+                options.adjacency = match value().as_str() {
+                    "orthogonal" => Adjacency::Orthogonal,
+                    "diagonal" => Adjacency::Diagonal,
+                    _ => print_usage_exit(&cmd_name)
+                };
+            },
+            _ => input_file = Some(a)
         }
-
-        total
     }
 
-    fn gear_ratio_total(&self) -> u32 {
-        let mut total = 0;
-
-        for (i, cell) in self.cells.iter().enumerate() {
-            if let Cell::Symbol { content: '*' } = cell {
-                let numbers = self.adjacent_numbers(i);
-                if numbers.len() == 2 {
-                    total += numbers.iter().product::<u32>();
-                }
-            }
-        }
+    let input_file = input_file.unwrap_or_else(|| print_usage_exit(&cmd_name));
 
-        total
+    output.set_input(&input_file);
+    if run_part2 {
+        output.set_part(2);
     }
 
-    // FIXME this doesn't account for numbers that are adjacent to multiple
-    // symbols, it will include those numbers twice in the results.
-    // This doesn't seem to matter with the puzzle input data, but it bugs me.
-    fn adjacent_numbers(&self, index: usize) -> HashSet<u32> {
-        let point = self.index_to_point(index);
-
-        let limit_x = (self.cols - 1) as i32;
-        let limit_y = (self.rows - 1) as i32;
-
-        let mut search: Vec<Point> = vec![];
-
-        let x_range = (point.x - 1) .. (point.x + 2);
-
-        // generate search point rect around symbol from top left
-        search.extend(x_range.clone().map(|x| Point { x, y: point.y - 1 }));
-        search.push(Point { x: point.x + 1, y: point.y });
-        search.extend(x_range.map(|x| Point { x, y: point.y + 1 }));
-        search.push(Point { x: point.x - 1, y: point.y });
-
-        let mut result: HashSet<u32> = HashSet::new();
-
-        for p in search {
-            if p.x < 0 || p.x > limit_x || point.y < 0 || point.y > limit_y {
-                // skip if point out of range
-                continue;
-            }
-
-            let i = self.point_to_index(&p);
-            let val = self.number_at_index(i);
-            if let Some(val) = val {
-                result.insert(val);
-            }
-        }
+    let file_content = profile.phase("read", || {
+        read_to_string(&input_file).unwrap_or_else(|err| output.fail("read", err))
+    });
 
-        result
-    }
+    let board = profile.phase("parse", || Board::load(file_content, options))
+        .unwrap_or_else(|err| output.fail("load", err));
 
-    fn index_to_point(&self, index: usize) -> Point {
-        Point {
-            x: (index % self.cols) as i32,
-            y: (index / self.cols) as i32
+    let view = if overlay {
+        Some(board.overlay(ansi))
+    } else {
+        match report_format.as_deref() {
+            Some("table") => Some(board.report().to_string()),
+            Some("json") => {
+                let json = serde_json::to_string_pretty(&board.report())
+                    .expect("report should serialize to json");
+                Some(json + "\n")
+            },
+            Some(_) => print_usage_exit(&cmd_name),
+            None => None
         }
-    }
-
-    fn point_to_index(&self, point: &Point) -> usize {
-        point.y as usize * self.cols + point.x as usize
-    }
+    };
 
-    fn number_at_index(&self, index: usize) -> Option<u32> {
-        if let Cell::Number { .. } = &self.cells[index] {
-            let mut iter = self.cells[..=index].iter()
-                .enumerate()
-                .rev();
-
-            // iter reverse until cell is not number and start one forward
-            let start = iter.find_map(|(i, cell)| match cell {
-                Cell::Number { .. } => None,
-                _ => Some(i)
-            }).map_or(0, |i| i + 1);
-
-            // now collect/concat all number cells and parse into int
-            let number = self.cells[start..].iter()
-                .map_while(|cell| match cell {
-                    Cell::Number { content: c } => Some(c),
-                    _ => None
-                }).collect::<String>();
-
-            Some(number.parse::<u32>().expect("number should parse as u32"))
+    if let Some(view) = view {
+        // stdout only has the answer object in json format, so the view goes
+        // to stderr and the answer is still solved
+        if output.format() == Format::Json {
+            eprint!("{view}");
         } else {
-            None
+            print!("{view}");
+            return;
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn number_at_index() {
-        let board = Board::load("467..114..".into());
-
-        assert_eq!(board.number_at_index(1), Some(467));
-        assert_eq!(board.number_at_index(4), None);
-        assert_eq!(board.number_at_index(6), Some(114));
-    }
-}
-
-fn main() {
-    let mut args = env::args();
-
-    // first arg is command name
-    let cmd_name = args.next().unwrap();
-
-    let mut run_part2 = false;
-    let input_file = match args.next() {
-        Some(a) if a == "-p2" => {
-            run_part2 = true;
-            args.next()
-        },
-        Some(a) => Some(a),
-        None => None
-    }.unwrap_or_else(|| {
-        println!("{cmd_name} [input.txt]");
-        process::exit(1);
-    });
-
-    let file_content = read_to_string(input_file)
-        .expect("input file should exist and be text file");
-
-    let board = Board::load(file_content);
 
-    let total = if !run_part2 {
-        board.part_number_total()
+    let total = profile.phase("solve", || if !run_part2 {
+        Some(board.part_number_total())
     } else {
         board.gear_ratio_total()
-    };
+    }).unwrap_or_else(|| output.fail("solve", "Gear ratio total should fit in a u64"));
+
+    output.answer(total);
+    profile.report();
+}
 
-    println!("{total}");
+fn print_usage_exit(me: &str) -> ! {
+    println!("{me} [-p2] [--report table|json] [--overlay [--no-color]] [--symbols chars] [--gear char] \
+        [--gear-neighbours n] [--adjacency orthogonal|diagonal] [--time] [--mem] [--format text|json] [input.txt]");
+    process::exit(1)
 }
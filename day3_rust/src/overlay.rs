@@ -0,0 +1,150 @@
+use crate::{Board, Cell};
+
+/// How a cell is highlighted in the overlay
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Highlight {
+    Space,
+    Part,
+    NonPart,
+    /// Number that is one of the part numbers of a gear
+    GearNumber,
+    Gear,
+    /// Symbol with at least one adjacent number
+    Symbol,
+    /// Symbol without any adjacent numbers
+    LoneSymbol
+}
+
+impl Highlight {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Highlight::Space => "\x1b[2m",
+            Highlight::Part => "\x1b[32m",
+            Highlight::NonPart => "\x1b[31m",
+            Highlight::GearNumber => "\x1b[1;33m",
+            Highlight::Gear => "\x1b[1;33m",
+            Highlight::Symbol => "\x1b[36m",
+            Highlight::LoneSymbol => "\x1b[35m"
+        }
+    }
+
+    /// Code for the plain text overlay
+    fn code(&self) -> char {
+        match self {
+            Highlight::Space => '.',
+            Highlight::Part => 'P',
+            Highlight::NonPart => 'n',
+            Highlight::GearNumber => 'g',
+            Highlight::Gear => 'G',
+            Highlight::Symbol => 'S',
+            Highlight::LoneSymbol => 'L'
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+const LEGEND: &str = "\
+P part number, n not a part number, G gear, g gear part number,
+S symbol, L symbol without numbers";
+
+impl Board {
+    fn highlights(&self) -> Vec<Highlight> {
+        let mut is_part = vec![false; self.spans.len()];
+        let mut is_gear_number = vec![false; self.spans.len()];
+        let mut highlights = vec![Highlight::Space; self.cells.len()];
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            if let Cell::Symbol { .. } = cell {
+                let spans = self.adjacent_spans(i);
+
+                highlights[i] = if self.is_gear_symbol(cell) &&
+                    spans.len() == self.options.gear_neighbours
+                {
+                    spans.iter().for_each(|id| is_gear_number[*id] = true);
+                    Highlight::Gear
+                } else if spans.is_empty() {
+                    Highlight::LoneSymbol
+                } else {
+                    Highlight::Symbol
+                };
+
+                spans.iter().for_each(|id| is_part[*id] = true);
+            }
+        }
+
+        for (i, id) in self.span_ids.iter().enumerate() {
+            if let Some(id) = id {
+                highlights[i] = if is_gear_number[*id] {
+                    Highlight::GearNumber
+                } else if is_part[*id] {
+                    Highlight::Part
+                } else {
+                    Highlight::NonPart
+                };
+            }
+        }
+
+        highlights
+    }
+
+    /// Render the schematic with numbers and symbols coloured by how they
+    /// contribute to the answers. Without ANSI colour each row of the
+    /// schematic is followed by a row of highlight codes.
+    pub fn overlay(&self, ansi: bool) -> String {
+        let highlights = self.highlights();
+        let mut out = String::new();
+
+        let rows = self.cells.rows_iter()
+            .zip(highlights.chunks(self.cells.cols()));
+
+        for (cells, highlights) in rows {
+            let chars = cells.iter().map(|cell| match cell {
+                Cell::Space => '.',
+                Cell::Number { content } | Cell::Symbol { content } => *content
+            });
+
+            if ansi {
+                for (c, highlight) in chars.zip(highlights) {
+                    out.push_str(highlight.ansi());
+                    out.push(c);
+                    out.push_str(RESET);
+                }
+            } else {
+                out.extend(chars);
+                out.push_str("  ");
+                out.extend(highlights.iter().map(|h| h.code()));
+            }
+
+            out.push('\n');
+        }
+
+        if !ansi {
+            out.push('\n');
+            out.push_str(LEGEND);
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Options};
+
+    #[test]
+    fn plain_overlay() {
+        let board = Board::load("5.5.#\n.*...\n...+1\n9....".into(), Options::default()).unwrap();
+
+        let overlay = board.overlay(false);
+        let rows: Vec<_> = overlay.lines().take(4).collect();
+
+        assert_eq!(rows, vec![
+            "5.5.#  g.g.L",
+            ".*...  .G...",
+            "...+1  ...SP",
+            "9....  n...."
+        ]);
+    }
+}
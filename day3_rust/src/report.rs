@@ -0,0 +1,155 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{Board, Cell};
+
+/// Every symbol, number and gear candidate of a schematic, with the numbers
+/// they are attached to
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub symbols: Vec<SymbolEntry>,
+    pub numbers: Vec<NumberEntry>,
+    pub gears: Vec<GearEntry>
+}
+
+#[derive(Debug, Serialize)]
+pub struct SymbolEntry {
+    pub symbol: char,
+    pub x: i32,
+    pub y: i32,
+    pub part_numbers: Vec<u32>
+}
+
+#[derive(Debug, Serialize)]
+pub struct NumberEntry {
+    pub value: u32,
+    pub row: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+    pub is_part: bool
+}
+
+/// A gear symbol, which only has a ratio when it has exactly the number of
+/// neighbours required by the options and the ratio fits in a u64
+#[derive(Debug, Serialize)]
+pub struct GearEntry {
+    pub x: i32,
+    pub y: i32,
+    pub neighbours: usize,
+    pub ratio: Option<u64>
+}
+
+impl Board {
+    pub fn report(&self) -> Report {
+        let mut symbols = vec![];
+        let mut gears = vec![];
+        let mut is_part = vec![false; self.spans.len()];
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            let Cell::Symbol { content } = cell else {
+                continue;
+            };
+
+            let point = self.cells.index_to_point(i);
+            let spans = self.adjacent_spans(i);
+
+            for id in &spans {
+                is_part[*id] = true;
+            }
+
+            let part_numbers: Vec<_> = spans.iter()
+                .map(|id| self.spans[*id].value)
+                .collect();
+
+            if self.is_gear_symbol(cell) {
+                gears.push(GearEntry {
+                    x: point.x,
+                    y: point.y,
+                    neighbours: part_numbers.len(),
+                    ratio: if part_numbers.len() == self.options.gear_neighbours {
+                        self.gear_ratio(&spans)
+                    } else {
+                        None
+                    }
+                });
+            }
+
+            symbols.push(SymbolEntry {
+                symbol: *content,
+                x: point.x,
+                y: point.y,
+                part_numbers
+            });
+        }
+
+        let numbers = self.spans.iter()
+            .map(|span| NumberEntry {
+                value: span.value,
+                row: span.row,
+                col_start: span.cols.start,
+                col_end: span.cols.end - 1,
+                is_part: is_part[span.id]
+            })
+            .collect();
+
+        Report { symbols, numbers, gears }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Symbols")?;
+        writeln!(f, "{:>5} {:>5}  {:<6}  part numbers", "x", "y", "symbol")?;
+        for s in &self.symbols {
+            let numbers: Vec<_> = s.part_numbers.iter()
+                .map(|n| n.to_string())
+                .collect();
+
+            writeln!(f, "{:>5} {:>5}  {:<6}  {}", s.x, s.y, s.symbol, numbers.join(", "))?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Numbers")?;
+        writeln!(f, "{:>7} {:>5} {:>11}  part", "value", "row", "cols")?;
+        for n in &self.numbers {
+            let cols = format!("{}-{}", n.col_start, n.col_end);
+            let part = if n.is_part { "yes" } else { "no" };
+
+            writeln!(f, "{:>7} {:>5} {:>11}  {part}", n.value, n.row, cols)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Gears")?;
+        writeln!(f, "{:>5} {:>5}  {:>10}  ratio", "x", "y", "neighbours")?;
+        for g in &self.gears {
+            let ratio = g.ratio.map_or("-".to_string(), |r| r.to_string());
+
+            writeln!(f, "{:>5} {:>5}  {:>10}  {ratio}", g.x, g.y, g.neighbours)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+
+    #[test]
+    fn report_entries() {
+        let report = Board::load("5.5.\n.*..\n...7".into(), Options::default()).unwrap().report();
+
+        assert_eq!(report.symbols.len(), 1);
+        assert_eq!(report.symbols[0].part_numbers, vec![5, 5]);
+
+        let parts: Vec<_> = report.numbers.iter()
+            .map(|n| (n.value, n.is_part))
+            .collect();
+        assert_eq!(parts, vec![(5, true), (5, true), (7, false)]);
+
+        assert_eq!(report.gears.len(), 1);
+        assert_eq!((report.gears[0].neighbours, report.gears[0].ratio), (2, Some(25)));
+    }
+}
@@ -0,0 +1,38 @@
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day5::Almanac;
+
+const SIZES: [usize; 3] = [10, 100, 1000];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day5/load");
+
+    for (size, input) in sized_inputs(5, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter_batched(|| input.clone(), |input| Almanac::load(input, true).unwrap(), BatchSize::SmallInput)
+        });
+    }
+
+    group.finish();
+}
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day5/solve");
+
+    for (size, input) in sized_inputs(5, &SIZES) {
+        let seeds = Almanac::load(input.clone(), false).unwrap();
+        let seed_ranges = Almanac::load(input, true).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("part1", size), &seeds, |b, almanac| {
+            b.iter(|| almanac.find_lowest_location())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &seed_ranges, |b, almanac| {
+            b.iter(|| almanac.find_lowest_location())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
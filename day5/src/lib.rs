@@ -0,0 +1,261 @@
+//! Mapping seeds through almanac categories to their locations
+
+use std::{cmp::min, error, fmt, ops::Range, str::FromStr};
+
+pub struct Almanac {
+    pub seeds: Vec<Range<u64>>,
+    categories: Vec<Category>
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
+enum CategoryName {
+    SeedToSoil,
+    SoilToFertilizer,
+    FertilizerToWater,
+    WaterToLight,
+    LightToTemp,
+    TempToHumidity,
+    HumidityToLocation
+}
+
+impl FromStr for CategoryName {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<CategoryName, Self::Err> {
+        match input {
+            "seed-to-soil" => Ok(CategoryName::SeedToSoil),
+            "soil-to-fertilizer" => Ok(CategoryName::SoilToFertilizer),
+            "fertilizer-to-water" => Ok(CategoryName::FertilizerToWater),
+            "water-to-light" => Ok(CategoryName::WaterToLight),
+            "light-to-temperature" => Ok(CategoryName::LightToTemp),
+            "temperature-to-humidity" => Ok(CategoryName::TempToHumidity),
+            "humidity-to-location" => Ok(CategoryName::HumidityToLocation),
+            _ => Err(())
+        }
+    }
+}
+
+/// Reasons an almanac can't be loaded, line numbers start at 1
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    MissingSeeds,
+    InvalidNumber {
+        line: usize,
+        text: String
+    },
+    UnknownCategory {
+        line: usize,
+        name: String
+    },
+    /// Map line should have a destination start, source start and length
+    MapLength {
+        line: usize,
+        found: usize
+    },
+    /// Range ends past the largest u64
+    RangeOverflow {
+        line: usize
+    },
+    /// Seed ranges for part 2 should be pairs of start and length
+    OddSeedCount {
+        line: usize
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::MissingSeeds => write!(f, "Almanac should have a 'seeds:' line"),
+            LoadError::InvalidNumber { line, text } => {
+                write!(f, "Line {line}: '{text}' should be a positive integer")
+            },
+            LoadError::UnknownCategory { line, name } => {
+                write!(f, "Line {line}: unknown category '{name}'")
+            },
+            LoadError::MapLength { line, found } => {
+                write!(f, "Line {line}: map should have 3 numbers, found {found}")
+            },
+            LoadError::RangeOverflow { line } => write!(f, "Line {line}: range is too large"),
+            LoadError::OddSeedCount { line } => {
+                write!(f, "Line {line}: seeds should be pairs of start and length")
+            }
+        }
+    }
+}
+
+impl error::Error for LoadError { }
+
+fn parse_number(line: usize, text: &str) -> Result<u64, LoadError> {
+    text.parse::<u64>()
+        .map_err(|_| LoadError::InvalidNumber { line, text: text.into() })
+}
+
+fn range(line: usize, start: u64, len: u64) -> Result<Range<u64>, LoadError> {
+    let end = start.checked_add(len).ok_or(LoadError::RangeOverflow { line })?;
+    Ok(start..end)
+}
+
+struct Category {
+    _name: CategoryName,
+    maps: Vec<CategoryMap>
+}
+
+struct CategoryMap {
+    src: Range<u64>,
+    dst: Range<u64>
+}
+
+impl Almanac {
+    pub fn load(file_data: String, part2: bool) -> Result<Self, LoadError> {
+        // line numbers start at 1
+        let mut lines = file_data.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let mut seeds: Option<Vec<Range<u64>>> = None;
+        let mut categories: Vec<Category> = vec![];
+
+        loop {
+            match lines.next() {
+                Some((n, line)) if line.starts_with("seeds:") => {
+                    let parts = line["seeds:".len()..]
+                        .split_whitespace()
+                        .map(|s| parse_number(n, s))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    if part2 {
+                        if parts.len() % 2 != 0 {
+                            return Err(LoadError::OddSeedCount { line: n });
+                        }
+
+                        seeds = Some(parts.chunks(2)
+                            .map(|c| range(n, c[0], c[1]))
+                            .collect::<Result<_, _>>()?);
+                    } else {
+                        seeds = Some(parts.iter()
+                            .map(|s| range(n, *s, 1))
+                            .collect::<Result<_, _>>()?);
+                    }
+                },
+                Some((n, line)) if line.ends_with("map:") => {
+                    let name = line.replace(" map:", "");
+                    let category = name.parse::<CategoryName>()
+                        .map_err(|_| LoadError::UnknownCategory { line: n, name })?;
+
+                    let mut category_maps: Vec<CategoryMap> = vec![];
+
+                    loop {
+                        let (n, line) = match lines.next() {
+                            Some((n, line)) if !line.is_empty() => (n, line),
+                            _ => break
+                        };
+
+                        let parts = line.split(" ")
+                            .map(|s| parse_number(n, s))
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        let [dst_start, src_start, len] = parts[..] else {
+                            return Err(LoadError::MapLength { line: n, found: parts.len() });
+                        };
+
+                        let map = CategoryMap {
+                            src: range(n, src_start, len)?,
+                            dst: range(n, dst_start, len)?
+                        };
+
+                        category_maps.push(map);
+                    }
+
+                    categories.push(Category {
+                        _name: category,
+                        maps: category_maps
+                    });
+                },
+                Some(_) => {
+                    // ruh-roh, line not handled... unless intended "of course"
+                },
+                None => break
+            }
+        }
+
+        Ok(Almanac {
+            seeds: seeds.ok_or(LoadError::MissingSeeds)?,
+            categories
+        })
+    }
+
+    pub fn find_lowest_location(&self) -> u64 {
+        let mut loc = u64::MAX;
+
+        for seed_range in self.seeds.iter() {
+            for seed in seed_range.clone() {
+                loc = min(loc, self.seed_to_location(seed));
+            }
+        }
+
+        loc
+    }
+
+    fn seed_to_location(&self, seed: u64) -> u64 {
+        let mut val = seed;
+
+        for category in &self.categories {
+            val = Almanac::map_value(category, val);
+        }
+
+        val
+    }
+
+    fn map_value(category: &Category, val:u64) -> u64 {
+        for map in &category.maps {
+            if map.src.contains(&val) {
+                return map.dst.start + (val - map.src.start);
+            }
+        }
+
+        val
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_errors() {
+        let load = |data: &str, part2| Almanac::load(data.into(), part2).err();
+
+        assert_eq!(load("seed-to-soil map:\n1 2 3\n", false), Some(LoadError::MissingSeeds));
+        assert_eq!(
+            load("seeds: 79 x4\n", false),
+            Some(LoadError::InvalidNumber { line: 1, text: "x4".into() })
+        );
+        assert_eq!(load("seeds: 79 14 55\n", true), Some(LoadError::OddSeedCount { line: 1 }));
+        assert_eq!(
+            load("seeds: 79\n\nseed-to-dirt map:\n", false),
+            Some(LoadError::UnknownCategory { line: 3, name: "seed-to-dirt".into() })
+        );
+        assert_eq!(
+            load("seeds: 79\n\nseed-to-soil map:\n50 98 2\n52 50\n", false),
+            Some(LoadError::MapLength { line: 5, found: 2 })
+        );
+        assert_eq!(
+            load("seeds: 79\n\nseed-to-soil map:\n50 -98 2\n", false),
+            Some(LoadError::InvalidNumber { line: 4, text: "-98".into() })
+        );
+        assert_eq!(
+            load("seeds: 18446744073709551615 2\n", true),
+            Some(LoadError::RangeOverflow { line: 1 })
+        );
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(5, seed, 20).unwrap();
+
+            for (part2, expected) in [(false, generated.part1), (true, generated.part2)] {
+                let almanac = Almanac::load(generated.input.clone(), part2).unwrap();
+                assert_eq!(Some(almanac.find_lowest_location().to_string()), expected);
+            }
+        }
+    }
+}
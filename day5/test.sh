@@ -1,9 +1,7 @@
 #!/bin/bash
 
-cargo build --release
-
-CMD="${PWD}/target/release/day5"
-CMD2="${PWD}/target/release/day5 -p2"
+CMD="cargo run -q --release"
+CMD2="cargo run -q --release -- -p2"
 source ../tests/run.sh
 
-run_day_tests day5
\ No newline at end of file
+run_day_tests day5
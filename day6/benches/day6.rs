@@ -0,0 +1,42 @@
+use std::hint::black_box;
+
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day6::{load_race_results, load_single_race_result, part1, part2};
+
+/// Number of races, the part 2 race gets much longer with each one
+const SIZES: [usize; 3] = [1, 2, 3];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day6/load");
+
+    for (size, input) in sized_inputs(6, &SIZES) {
+        group.bench_with_input(BenchmarkId::new("races", size), &input, |b, input| {
+            b.iter(|| load_race_results(black_box(input)))
+        });
+        group.bench_with_input(BenchmarkId::new("single_race", size), &input, |b, input| {
+            b.iter(|| load_single_race_result(black_box(input)))
+        });
+    }
+
+    group.finish();
+}
+
+/// Parts load their own races, so these include parsing
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day6/solve");
+
+    for (size, input) in sized_inputs(6, &SIZES) {
+        group.bench_with_input(BenchmarkId::new("part1", size), &input, |b, input| {
+            b.iter(|| part1(input).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &input, |b, input| {
+            b.iter(|| part2(input).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
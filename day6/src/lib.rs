@@ -0,0 +1,172 @@
+//! Ways to beat the record distance of boat races
+
+use std::{error, fmt};
+
+#[derive(Debug)]
+pub struct RaceResult {
+    duration: u64,
+    distance: u64
+}
+
+impl RaceResult {
+    fn new(race_duration: u64, distance: u64) -> Self {
+        RaceResult { duration: race_duration, distance }
+    }
+
+    fn from_button_press(race_duration: u64, button_duration: u64) -> Self {
+        // boat speed is X mm/s where X = button duration
+        let remaining_duration = race_duration - button_duration;
+        let mm_sec = button_duration;
+
+        RaceResult {
+            duration: race_duration,
+            distance: remaining_duration * mm_sec
+        }
+    }
+
+    pub fn count_winnable_button_durations(&self) -> usize {
+        (1..self.duration)
+            .map(|d| RaceResult::from_button_press(self.duration, d))
+            .filter(|r| r.distance > self.distance)
+            .count()
+    }
+
+    // fn get_button_press_results(&self) -> impl Iterator<Item = Self>
+    // {
+    //     (1..self.duration).into_iter()
+    //         .map(|d| RaceResult::from_button_press(self.duration, d))
+    // }
+}
+
+/// Reasons the race results can't be loaded
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    /// Line number (starting at 1) and text that isn't a positive integer
+    InvalidNumber {
+        line: usize,
+        text: String
+    },
+    /// Label of the line that wasn't found
+    MissingLine(&'static str),
+    CountMismatch {
+        times: usize,
+        distances: usize
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::InvalidNumber { line, text } => {
+                write!(f, "Line {line}: '{text}' should be a positive integer")
+            },
+            LoadError::MissingLine(label) => write!(f, "Missing '{label}' line"),
+            LoadError::CountMismatch { times, distances } => {
+                write!(f, "{times} times should match {distances} distances")
+            }
+        }
+    }
+}
+
+impl error::Error for LoadError { }
+
+/// Finds the line starting with `label`, returning its line number and the
+/// numbers after the label
+fn find_line<'a>(data: &'a str, label: &'static str)
+    -> Result<(usize, impl Iterator<Item = &'a str>), LoadError>
+{
+    data.lines()
+        .enumerate()
+        .find_map(|(i, line)| line.strip_prefix(label).map(|rest| (i + 1, rest)))
+        .map(|(n, rest)| (n, rest.split_whitespace()))
+        .ok_or(LoadError::MissingLine(label))
+}
+
+fn parse_number(line: usize, text: &str) -> Result<u64, LoadError> {
+    text.parse::<u64>()
+        .map_err(|_| LoadError::InvalidNumber { line, text: text.into() })
+}
+
+fn parse_numbers(data: &str, label: &'static str) -> Result<Vec<u64>, LoadError> {
+    let (n, numbers) = find_line(data, label)?;
+    numbers.map(|s| parse_number(n, s)).collect()
+}
+
+/// Number on the line with the spaces between digits removed
+fn parse_joined_number(data: &str, label: &'static str) -> Result<u64, LoadError> {
+    let (n, numbers) = find_line(data, label)?;
+    parse_number(n, &numbers.collect::<String>())
+}
+
+pub fn load_race_results(data: &str) -> Result<Vec<RaceResult>, LoadError> {
+    let times = parse_numbers(data, "Time:")?;
+    let distances = parse_numbers(data, "Distance:")?;
+
+    if times.len() != distances.len() {
+        return Err(LoadError::CountMismatch { times: times.len(), distances: distances.len() });
+    }
+
+    Ok(times.into_iter()
+        .zip(distances)
+        .map(|(time, distance)| RaceResult::new(time, distance))
+        .collect())
+}
+
+pub fn load_single_race_result(data: &str) -> Result<RaceResult, LoadError> {
+    let time = parse_joined_number(data, "Time:")?;
+    let distance = parse_joined_number(data, "Distance:")?;
+
+    Ok(RaceResult::new(time, distance))
+}
+
+pub fn part1(file_content: &str) -> Result<usize, LoadError> {
+    Ok(winnable_product(&load_race_results(file_content)?))
+}
+
+pub fn winnable_product(races: &[RaceResult]) -> usize {
+    let mut total = 1usize;
+
+    for race in races {
+        total *= race.count_winnable_button_durations();
+    }
+
+    total
+}
+
+pub fn part2(file_content: &str) -> Result<usize, LoadError> {
+    let result = load_single_race_result(file_content)?;
+    Ok(result.count_winnable_button_durations())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_errors() {
+        assert_eq!(part1("Distance:  9  40  200\n").err(), Some(LoadError::MissingLine("Time:")));
+        assert_eq!(part2("Time:      7  15   30\n").err(), Some(LoadError::MissingLine("Distance:")));
+        assert_eq!(
+            part1("Time:      7  15   30\nDistance:  9  4o  200\n").err(),
+            Some(LoadError::InvalidNumber { line: 2, text: "4o".into() })
+        );
+        assert_eq!(
+            part2("Time:      7  15   -30\nDistance:  9  40  200\n").err(),
+            Some(LoadError::InvalidNumber { line: 1, text: "715-30".into() })
+        );
+        assert_eq!(
+            part1("Time:      7  15   30\nDistance:  9  40\n").err(),
+            Some(LoadError::CountMismatch { times: 3, distances: 2 })
+        );
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(6, seed, 3).unwrap();
+
+            assert_eq!(part1(&generated.input).ok().map(|t| t.to_string()), generated.part1);
+            assert_eq!(part2(&generated.input).ok().map(|t| t.to_string()), generated.part2);
+        }
+    }
+}
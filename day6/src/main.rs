@@ -1,94 +1,14 @@
 use std::{env, process, fs::read_to_string};
 
-#[derive(Debug)]
-struct RaceResult {
-    duration: u64,
-    distance: u64
-}
-
-impl RaceResult {
-    fn new(race_duration: u64, distance: u64) -> Self {
-        RaceResult { duration: race_duration, distance }
-    }
-
-    fn from_button_press(race_duration: u64, button_duration: u64) -> Self {
-        // boat speed is X mm/s where X = button duration
-        let remaining_duration = race_duration - button_duration;
-        let mm_sec = button_duration;
-
-        RaceResult {
-            duration: race_duration,
-            distance: remaining_duration * mm_sec
-        }
-    }
-
-    fn count_winnable_button_durations(&self) -> usize {
-        (1..self.duration).into_iter()
-            .map(|d| RaceResult::from_button_press(self.duration, d))
-            .filter(|r| r.distance > self.distance)
-            .count()
-    }
-
-    // fn get_button_press_results(&self) -> impl Iterator<Item = Self>
-    // {
-    //     (1..self.duration).into_iter()
-    //         .map(|d| RaceResult::from_button_press(self.duration, d))
-    // }
-}
-
-fn load_race_results(data: &str) -> Vec<RaceResult> {
-    let mut times: Vec<u64> = vec![];
-    let mut distances: Vec<u64> = vec![];
-
-    for line in data.lines() {
-        if line.starts_with("Time:") {
-            times = line.replace("Time:", "")
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect();
-        }
-        if line.starts_with("Distance:") {
-            distances = line.replace("Distance:", "")
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .map(|s| s.parse::<u64>().unwrap())
-                .collect();
-        }
-    }
-
-    times.into_iter()
-        .zip(distances)
-        .map(|(time, distance)| RaceResult::new(time, distance))
-        .collect()
-}
-
-fn load_single_race_result(data: &str) -> RaceResult {
-    let mut time: u64 = 0;
-    let mut distance: u64 = 0;
+use common::{alloc::CountingAlloc, output::Output, profile::Profile};
+use day6::{load_race_results, load_single_race_result, winnable_product};
 
-    for line in data.lines() {
-        if line.starts_with("Time:") {
-            time = line.replace("Time:", "")
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .collect::<String>()
-                .parse::<u64>().unwrap();
-        }
-        if line.starts_with("Distance:") {
-            distance = line.replace("Distance:", "")
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .collect::<String>()
-                .parse::<u64>().unwrap();
-        }
-    }
-
-    RaceResult::new(time, distance)
-}
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
 
 fn main() {
-    let mut args = env::args();
+    let (mut profile, args) = Profile::from_args(env::args());
+    let (output, mut args) = Output::from_args(6, args);
 
     // first arg is command name
     let cmd_name = args.next().unwrap();
@@ -103,35 +23,30 @@ fn main() {
         None => None
     }.unwrap_or_else(|| print_usage_exit(&cmd_name));
 
-    let file_content = read_to_string(input_file)
-        .expect("input file should exist and be text file");
+    output.set_input(&input_file);
+    if run_part2 {
+        output.set_part(2);
+    }
+
+    let file_content = profile.phase("read", || {
+        read_to_string(&input_file).unwrap_or_else(|err| output.fail("read", err))
+    });
 
     let total = if run_part2 {
-        part2(&file_content)
+        let race = profile.phase("parse", || load_single_race_result(&file_content))
+            .unwrap_or_else(|err| output.fail("load", err));
+        profile.phase("solve", || race.count_winnable_button_durations())
     } else {
-        part1(&file_content)
+        let races = profile.phase("parse", || load_race_results(&file_content))
+            .unwrap_or_else(|err| output.fail("load", err));
+        profile.phase("solve", || winnable_product(&races))
     };
 
-    println!("{total}");
+    output.answer(total);
+    profile.report();
 }
 
 fn print_usage_exit(me: &str) -> ! {
-    println!("{me} [-p2] input.txt");
+    println!("{me} [-p2] [--time] [--mem] [--format text|json] input.txt");
     process::exit(1)
 }
-
-fn part1(file_content: &str) -> usize {
-    let mut total = 1usize;
-    let results = load_race_results(file_content);
-
-    for race in results {
-        total *= race.count_winnable_button_durations();
-    }
-
-    total
-}
-
-fn part2(file_content: &str) -> usize {
-    let result = load_single_race_result(file_content);
-    result.count_winnable_button_durations()
-}
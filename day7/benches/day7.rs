@@ -0,0 +1,55 @@
+use std::hint::black_box;
+
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day7::{get_hand_type, get_hand_type_wildcard, total_hand_winnings, Card, Hand};
+
+const SIZES: [usize; 3] = [100, 1000, 10000];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day7/load");
+
+    for (size, input) in sized_inputs(7, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| black_box(input).lines().map(Hand::parse).collect::<Vec<_>>())
+        });
+    }
+
+    group.finish();
+}
+
+/// Hands are read as they are ranked, so this includes parsing. Part 2 is
+/// the same solver built with the `wildcard` feature.
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day7/solve");
+
+    for (size, input) in sized_inputs(7, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| total_hand_winnings(input.as_bytes()).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn hand_type(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day7/hand_type");
+
+    for (size, input) in sized_inputs(7, &SIZES) {
+        let hands: Vec<Vec<Card>> = input.lines()
+            .map(|line| line[..5].chars().map(|c| c.to_string().parse().unwrap()).collect())
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("plain", size), &hands, |b, hands| {
+            b.iter(|| hands.iter().map(get_hand_type).max())
+        });
+        group.bench_with_input(BenchmarkId::new("wildcard", size), &hands, |b, hands| {
+            b.iter(|| hands.iter().map(get_hand_type_wildcard).max())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve, hand_type);
+criterion_main!(benches);
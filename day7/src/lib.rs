@@ -0,0 +1,274 @@
+//! Winnings from ranking hands of camel cards
+
+use std::{
+    cmp::Ordering, collections::{BTreeMap, HashSet}, error, fmt, io::BufRead, str::FromStr
+};
+
+use common::input::{self, LineError};
+
+/// Hand types ordered weakest to strongest
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfKind,
+    FullHouse,
+    FourOfKind,
+    FiveOfKind
+}
+
+/// Cards orderd weakest to strongest
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub enum Card {
+    Two = 2,
+    Three = 3,
+    Four = 4,
+    Five = 5,
+    Six = 6,
+    Seven = 7,
+    Eight = 8,
+    Nine = 9,
+    T = 10,
+    #[cfg(feature="wildcard")]
+    J = 1,
+    #[cfg(not(feature="wildcard"))]
+    J = 11,
+    Q = 12,
+    K = 13,
+    A = 14
+}
+
+impl FromStr for Card {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<Card, Self::Err> {
+        match input {
+            "2" => Ok(Card::Two),
+            "3" => Ok(Card::Three),
+            "4" => Ok(Card::Four),
+            "5" => Ok(Card::Five),
+            "6" => Ok(Card::Six),
+            "7" => Ok(Card::Seven),
+            "8" => Ok(Card::Eight),
+            "9" => Ok(Card::Nine),
+            "T" => Ok(Card::T),
+            "J" => Ok(Card::J),
+            "Q" => Ok(Card::Q),
+            "K" => Ok(Card::K),
+            "A" => Ok(Card::A),
+            _ => Err(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Hand {
+    cards: Vec<Card>,
+    bid: u32,
+    type_: HandType
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let type_ord = self.type_.cmp(&other.type_);
+        if type_ord.is_eq() {
+            self.cards.cmp(&other.cards)
+        } else {
+            type_ord
+        }
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseHandError {
+    InvalidCard(char),
+    CardCount(usize),
+    MissingBid,
+    InvalidBid(String)
+}
+
+impl fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseHandError::InvalidCard(c) => write!(f, "card '{c}' should be one of 23456789TJQKA"),
+            ParseHandError::CardCount(n) => write!(f, "hand should have 5 cards, found {n}"),
+            ParseHandError::MissingBid => write!(f, "line should be 'cards bid'"),
+            ParseHandError::InvalidBid(s) => write!(f, "bid '{s}' should be positive integer")
+        }
+    }
+}
+
+impl error::Error for ParseHandError { }
+
+impl Hand {
+    pub fn parse(line: &str) -> Result<Self, ParseHandError> {
+        let (cards, bid) = line.split_once(' ')
+            .ok_or(ParseHandError::MissingBid)?;
+
+        let cards = cards.chars()
+            .map(|c| Card::from_str(c.to_string().as_str())
+                .map_err(|_| ParseHandError::InvalidCard(c)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if cards.len() != 5 {
+            return Err(ParseHandError::CardCount(cards.len()));
+        }
+
+        let bid = bid.parse::<u32>()
+            .map_err(|_| ParseHandError::InvalidBid(bid.into()))?;
+
+        let type_ = if cfg!(feature="wildcard") {
+            get_hand_type_wildcard(&cards)
+        } else {
+            get_hand_type(&cards)
+        };
+
+        Ok(Hand { cards, bid, type_ })
+    }
+}
+
+/// Bids of every hand with the same cards, kept in input order so ties are
+/// ranked the same way as a stable sort of the full list
+#[derive(Debug, Default)]
+struct BidTally {
+    count: usize,
+    bid_sum: usize,
+    /// Sum of each bid times the number of equal hands before it
+    weighted: usize
+}
+
+impl BidTally {
+    fn add(&mut self, bid: u32) {
+        self.weighted += self.count * bid as usize;
+        self.bid_sum += bid as usize;
+        self.count += 1;
+    }
+}
+
+pub fn get_hand_type_wildcard(cards: &Vec<Card>) -> HandType {
+    if cards.contains(&Card::J) {
+        let all_cards = [
+            Card::Two, Card::Three, Card::Four, Card::Five, Card::Six,
+            Card::Seven, Card::Eight, Card::Nine, Card::T, Card::Q, Card::K,
+            Card::A
+        ];
+
+        let mut possible_hands: Vec<HandType> = vec![];
+
+        for card in all_cards {
+            let cards: Vec<_> = cards.iter()
+                .map(|c| match c {
+                    Card::J => card,
+                    c => *c
+                })
+                .collect();
+
+            possible_hands.push(get_hand_type(&cards));
+        }
+
+        possible_hands.sort(); // sort weakest to strongest
+
+        *possible_hands.last().unwrap() // take strongest possible
+    } else {
+        get_hand_type(cards)
+    }
+}
+
+pub fn get_hand_type(cards: &Vec<Card>) -> HandType {
+    let set: HashSet<&Card> = HashSet::from_iter(cards);
+
+    if set.len() == 1 {
+        HandType::FiveOfKind
+    } else if set.len() == 2 {
+        // four of a kind or full house
+
+        let counts: Vec<_> = set.iter()
+            .map(|c| cards.iter().filter(|x| x == c).count())
+            .collect();
+
+        if counts.contains(&4) {
+            HandType::FourOfKind
+        } else {
+            HandType::FullHouse
+        }
+    } else if set.len() == 3 {
+        // three of a kind or two pair
+
+        let counts: Vec<_> = set.iter()
+            .map(|c| cards.iter().filter(|x| x == c).count())
+            .collect();
+
+        if counts.contains(&3) {
+            HandType::ThreeOfKind
+        } else {
+            HandType::TwoPair
+        }
+    } else if set.len() == 4 {
+        HandType::OnePair
+    } else {
+        HandType::HighCard
+    }
+}
+
+/// Reads one hand at a time, only keeping a tally for each distinct hand
+pub fn total_hand_winnings<R: BufRead>(input: R) -> Result<usize, LineError<ParseHandError>> {
+    // keyed weakest to strongest
+    let mut tallies: BTreeMap<(HandType, Vec<Card>), BidTally> = BTreeMap::new();
+
+    for line in input::lines(input) {
+        let hand = Hand::parse(&line?).map_err(LineError::Parse)?;
+
+        tallies.entry((hand.type_, hand.cards))
+            .or_default()
+            .add(hand.bid);
+    }
+
+    let mut total = 0;
+    let mut rank = 1;
+    for tally in tallies.values() {
+        total += rank * tally.bid_sum + tally.weighted;
+        rank += tally.count;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_hands_ranked_in_order() {
+        let input = "22345 10\nAKQ9T 1\n22345 100\n33444 7\n";
+
+        // AKQ9T 1, 22345 10, 22345 100, 33444 7
+        let expected = 1 + 2 * 10 + 3 * 100 + 4 * 7;
+        assert_eq!(total_hand_winnings(input.as_bytes()).ok(), Some(expected));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Hand::parse("22345").err(), Some(ParseHandError::MissingBid));
+        assert_eq!(Hand::parse("2234X 1").err(), Some(ParseHandError::InvalidCard('X')));
+        assert_eq!(Hand::parse("2234 1").err(), Some(ParseHandError::CardCount(4)));
+        assert_eq!(Hand::parse("22345 x").err(), Some(ParseHandError::InvalidBid("x".into())));
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(7, seed, 20).unwrap();
+
+            // part 2 rules are picked when building
+            let expected = if cfg!(feature = "wildcard") { generated.part2 } else { generated.part1 };
+            assert_eq!(total_hand_winnings(generated.input.as_bytes()).ok().map(|t| t.to_string()), expected);
+        }
+    }
+}
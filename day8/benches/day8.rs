@@ -0,0 +1,39 @@
+use std::hint::black_box;
+
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day8::{count_steps_multi_start, Network};
+
+const SIZES: [usize; 3] = [10, 100, 1000];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day8/load");
+
+    for (size, input) in sized_inputs(8, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| Network::load(black_box(input)))
+        });
+    }
+
+    group.finish();
+}
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day8/solve");
+
+    for (size, input) in sized_inputs(8, &SIZES) {
+        let net = Network::load(&input).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("part1", size), &net, |b, net| {
+            b.iter(|| net.count_steps("AAA", "ZZZ"))
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &net, |b, net| {
+            b.iter(|| count_steps_multi_start(net))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
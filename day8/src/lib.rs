@@ -0,0 +1,203 @@
+//! Steps through a network of nodes following left/right instructions
+
+use std::{collections::HashMap, error, fmt};
+
+use num::Integer;
+
+#[derive(Debug)]
+enum Instruction {
+    Left,
+    Right
+}
+
+impl TryFrom<char> for Instruction {
+    type Error = LoadError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            x => Err(LoadError::InvalidInstruction(x))
+        }
+    }
+}
+
+/// Reasons a network can't be loaded, line numbers start at 1
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoadError {
+    MissingInstructions,
+    InvalidInstruction(char),
+    InvalidNode {
+        line: usize,
+        text: String
+    },
+    /// Node leads to a label that has no line of its own
+    UnknownNode {
+        line: usize,
+        label: String
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::MissingInstructions => write!(f, "First line should be instructions"),
+            LoadError::InvalidInstruction(c) => write!(f, "Instruction '{c}' should be L or R"),
+            LoadError::InvalidNode { line, text } => {
+                write!(f, "Line {line}: '{text}' should be 'AAA = (BBB, CCC)'")
+            },
+            LoadError::UnknownNode { line, label } => {
+                write!(f, "Line {line}: node '{label}' isn't defined")
+            }
+        }
+    }
+}
+
+impl error::Error for LoadError { }
+
+pub struct Network {
+    instructions: Vec<Instruction>,
+    nodes: HashMap<String, (String, String)>
+}
+
+/// Split `AAA = (BBB, CCC)` into its three labels
+fn parse_node(line: &str) -> Option<(&str, &str, &str)> {
+    let (label, next_nodes) = line.split_once(" = ")?;
+    let (left, right) = next_nodes.strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_once(", ")?;
+
+    Some((label, left, right))
+}
+
+impl Network {
+    pub fn load(data: &str) -> Result<Self, LoadError> {
+        let mut lines = data.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let (_, inst) = lines.next().ok_or(LoadError::MissingInstructions)?;
+        let instructions = inst.chars()
+            .map(Instruction::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let mut nodes = HashMap::new();
+        let mut next_labels = vec![];
+
+        for (n, line) in lines {
+            // skip empty lines
+            if line.is_empty() {
+                continue;
+            }
+
+            let (label, left, right) = parse_node(line)
+                .ok_or_else(|| LoadError::InvalidNode { line: n, text: line.into() })?;
+
+            next_labels.push((n, left));
+            next_labels.push((n, right));
+            nodes.insert(label.into(), (left.into(), right.into()));
+        }
+
+        // every step should land on a node, so counting steps can't get lost
+        if let Some((n, label)) = next_labels.iter().find(|(_, l)| !nodes.contains_key(*l)) {
+            return Err(LoadError::UnknownNode { line: *n, label: label.to_string() });
+        }
+
+        Ok(Network { instructions, nodes })
+    }
+
+    pub fn count_steps(&self, start: &str, end: &str) -> usize {
+        let mut count = 0;
+        let mut next = start;
+
+        for inst in self.instructions.iter().cycle() {
+            count += 1;
+
+            let node = self.nodes.get(next).unwrap();
+            next = match inst {
+                Instruction::Left => node.0.as_str(),
+                Instruction::Right => node.1.as_str()
+            };
+
+            if next.ends_with(end) {
+                break;
+            }
+        }
+
+        count
+    }
+
+    // This seems to loop forever. According to what people are saying online,
+    // it might be possible to brute force the solution, but I didn't run
+    // this long enought to find out if it will work/finish.
+    fn _count_steps_multi_start<'a>(&'a self, start: &mut [&'a str], end: &str) -> usize {
+        let mut count = 0;
+
+        let next = start;
+
+        for inst in self.instructions.iter().cycle() {
+            count += 1;
+
+            for label in next.iter_mut() {
+                let node = self.nodes.get(*label).unwrap();
+                *label = match inst {
+                    Instruction::Left => node.0.as_ref(),
+                    Instruction::Right => node.1.as_ref()
+                }
+            }
+
+            if next.iter().all(|n| n.ends_with(end)) {
+                break;
+            }
+        }
+
+        count
+    }
+}
+
+// Sadly I failed to figure out this on my own.
+// I cheated and looked up how other people solved it.
+pub fn count_steps_multi_start(net: &Network) -> usize {
+    let start_steps: Vec<_> = net.nodes.keys()
+        .filter(|k| k.ends_with("A"))
+        .map(|k| k.as_str())
+        .collect();
+
+    start_steps.iter()
+        .map(|start| net.count_steps(start, "Z"))
+        .reduce(|a, b| a.lcm(&b))
+        .unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_errors() {
+        assert_eq!(Network::load("").err(), Some(LoadError::MissingInstructions));
+        assert_eq!(Network::load("LRX\n").err(), Some(LoadError::InvalidInstruction('X')));
+        assert_eq!(
+            Network::load("LR\n\nAAA = (BBB, CCC\n").err(),
+            Some(LoadError::InvalidNode { line: 3, text: "AAA = (BBB, CCC".into() })
+        );
+        assert_eq!(
+            Network::load("LR\n\nAAA = (AAA, ZZZ)\nZZZ (ZZZ, ZZZ)\n").err(),
+            Some(LoadError::InvalidNode { line: 4, text: "ZZZ (ZZZ, ZZZ)".into() })
+        );
+        assert_eq!(
+            Network::load("LR\n\nAAA = (AAA, ZZZ)\n").err(),
+            Some(LoadError::UnknownNode { line: 3, label: "ZZZ".into() })
+        );
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(8, seed, 20).unwrap();
+            let net = Network::load(&generated.input).unwrap();
+
+            assert_eq!(Some(net.count_steps("AAA", "ZZZ").to_string()), generated.part1);
+            assert_eq!(Some(count_steps_multi_start(&net).to_string()), generated.part2);
+        }
+    }
+}
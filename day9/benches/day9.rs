@@ -0,0 +1,43 @@
+use std::hint::black_box;
+
+use aoc::generate::sized_inputs;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day9::{parse_history, part1, part2, Polynomial};
+
+const SIZES: [usize; 3] = [100, 1000, 10000];
+
+fn load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day9/load");
+
+    for (size, input) in sized_inputs(9, &SIZES) {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| black_box(input).lines().map(parse_history).collect::<Vec<_>>())
+        });
+    }
+
+    group.finish();
+}
+
+/// Histories are read as they are extrapolated, so these include parsing
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day9/solve");
+
+    for (size, input) in sized_inputs(9, &SIZES) {
+        let histories: Vec<Vec<i32>> = input.lines().map(|line| parse_history(line).unwrap()).collect();
+
+        group.bench_with_input(BenchmarkId::new("part1", size), &input, |b, input| {
+            b.iter(|| part1(input.as_bytes()).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", size), &input, |b, input| {
+            b.iter(|| part2(input.as_bytes()).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("fit", size), &histories, |b, histories| {
+            b.iter(|| histories.iter().map(|h| Polynomial::fit(h).degree()).max())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, load, solve);
+criterion_main!(benches);
@@ -0,0 +1,311 @@
+//! Extrapolating histories of values
+
+use std::{error, fmt, io::BufRead, ops::Sub};
+
+use common::input::{self, LineError};
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseHistoryError {
+    Empty,
+    InvalidValue(String)
+}
+
+impl fmt::Display for ParseHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseHistoryError::Empty => write!(f, "history should have at least one value"),
+            ParseHistoryError::InvalidValue(s) => write!(f, "value '{s}' should be integer")
+        }
+    }
+}
+
+impl error::Error for ParseHistoryError { }
+
+pub fn parse_history(line: &str) -> Result<Vec<i32>, ParseHistoryError> {
+    if line.is_empty() {
+        return Err(ParseHistoryError::Empty);
+    }
+
+    line.split(' ')
+        .map(|s| s.parse::<i32>().map_err(|_| ParseHistoryError::InvalidValue(s.into())))
+        .collect()
+}
+
+pub fn part1<R: BufRead>(input: R) -> Result<i64, LineError<ParseHistoryError>> {
+    let mut total = 0;
+
+    for line in input::lines(input) {
+        let line = line?;
+        let history = parse_history(&line).map_err(LineError::Parse)?;
+
+        let diffs = generate_diffs(history.into_iter().map(i64::from).collect());
+
+        let next_value: i64 = diffs.iter()
+            .map(|s| s.last().unwrap())
+            .rev()
+            .scan(0, |acc, x| {
+                *acc += *x;
+                Some(*acc)
+            })
+            .last().unwrap();
+
+        total += next_value;
+    }
+
+    Ok(total)
+}
+
+pub fn part2<R: BufRead>(input: R) -> Result<i64, LineError<ParseHistoryError>> {
+    let mut total = 0;
+
+    for line in input::lines(input) {
+        let line = line?;
+        let history = parse_history(&line).map_err(LineError::Parse)?;
+
+        let diffs = generate_diffs(history.into_iter().map(i64::from).collect());
+
+        let first_value: i64 = diffs.iter()
+            .map(|s| s.first().unwrap())
+            .rev()
+            .scan(0, |acc, x| {
+                *acc = *x - *acc;
+                Some(*acc)
+            })
+            .last().unwrap();
+
+        total += first_value;
+    }
+
+    Ok(total)
+}
+
+/// Polynomial fitted to one history, with the values it gives just past
+/// either end
+pub struct Fit {
+    pub line: String,
+    pub polynomial: Polynomial,
+    pub next: BigRational,
+    pub previous: BigRational
+}
+
+/// Fit a polynomial to each history as it's read
+pub fn fits<R: BufRead>(input: R) -> impl Iterator<Item = Result<Fit, LineError<ParseHistoryError>>> {
+    input::lines(input).map(|line| {
+        let line = line?;
+        let history = parse_history(&line).map_err(LineError::Parse)?;
+        let polynomial = Polynomial::fit(&history);
+
+        Ok(Fit {
+            next: polynomial.eval(history.len() as i64),
+            previous: polynomial.eval(-1),
+            polynomial,
+            line
+        })
+    })
+}
+
+fn generate_diffs<T>(history: Vec<T>) -> Vec<Vec<T>>
+where
+    T: Zero,
+    for<'a> &'a T: Sub<&'a T, Output = T>
+{
+    let mut diffs: Vec<Vec<T>> = vec![history];
+
+    while let Some(history) = diff_pairs(diffs.last().unwrap()) {
+        diffs.push(history);
+    }
+
+    diffs
+}
+
+fn diff_pairs<T>(history: &[T]) -> Option<Vec<T>>
+where
+    T: Zero,
+    for<'a> &'a T: Sub<&'a T, Output = T>
+{
+    if history.iter().all(|n| n.is_zero()) {
+        None
+    } else {
+        Some(history.iter()
+            .zip(history.iter().skip(1))
+            .map(|(a, b)| b-a)
+            .collect())
+    }
+}
+
+/// Polynomial with exact rational coefficients, lowest power first, where
+/// `x` is the position of a value in the history (starting at zero)
+#[derive(Debug, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<BigRational>
+}
+
+impl Polynomial {
+    /// Find the minimal degree polynomial that generates the history.
+    ///
+    /// The first value of each row of differences is a coefficient of the
+    /// Newton forward difference form `sum(d[k] * C(x, k))`, which is then
+    /// expanded into plain powers of `x`.
+    pub fn fit(history: &[i32]) -> Self {
+        // differences of a long history soon outgrow any fixed size integer
+        let diffs = generate_diffs(history.iter().map(|n| BigInt::from(*n)).collect());
+
+        let mut coefficients = vec![BigRational::zero()];
+        // falling factorial x(x-1)...(x-k+1) divided by k!
+        let mut basis = vec![BigRational::one()];
+
+        for (k, row) in diffs.iter().enumerate() {
+            if let Some(first) = row.first() {
+                let scale = BigRational::from_integer(first.clone());
+
+                if coefficients.len() < basis.len() {
+                    coefficients.resize(basis.len(), BigRational::zero());
+                }
+
+                for (c, b) in coefficients.iter_mut().zip(&basis) {
+                    *c += &scale * b;
+                }
+            }
+
+            // basis *= (x - k) / (k + 1)
+            let k = BigRational::from_integer(BigInt::from(k));
+            let divisor = &k + BigRational::one();
+            let mut next = vec![BigRational::zero(); basis.len() + 1];
+            for (i, b) in basis.iter().enumerate() {
+                next[i + 1] += b / &divisor;
+                next[i] -= b * &k / &divisor;
+            }
+            basis = next;
+        }
+
+        while coefficients.len() > 1 && coefficients.last().unwrap().is_zero() {
+            coefficients.pop();
+        }
+
+        Polynomial { coefficients }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    pub fn eval(&self, x: i64) -> BigRational {
+        let x = BigRational::from_integer(BigInt::from(x));
+
+        self.coefficients.iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * &x + c)
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms = self.coefficients.iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .peekable();
+
+        if terms.peek().is_none() {
+            return write!(f, "0");
+        }
+
+        let mut first = true;
+        for (power, c) in terms {
+            if first {
+                if c.is_negative() {
+                    write!(f, "-")?;
+                }
+                first = false;
+            } else if c.is_negative() {
+                write!(f, " - ")?;
+            } else {
+                write!(f, " + ")?;
+            }
+
+            let c = c.abs();
+            match power {
+                0 => write!(f, "{c}")?,
+                _ if c.is_one() => write!(f, "x")?,
+                _ if c.is_integer() => write!(f, "{c}x")?,
+                _ => write!(f, "({c})x")?
+            }
+
+            if power > 1 {
+                write!(f, "^{power}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(n: i64, d: i64) -> BigRational {
+        BigRational::new(BigInt::from(n), BigInt::from(d))
+    }
+
+    #[test]
+    fn fit_polynomial() {
+        // (x + 1)(x + 2) / 2
+        let poly = Polynomial::fit(&[1, 3, 6, 10, 15, 21]);
+        assert_eq!(poly.coefficients, vec![ratio(1, 1), ratio(3, 2), ratio(1, 2)]);
+        assert_eq!(poly.degree(), 2);
+        assert_eq!(poly.eval(6), ratio(28, 1));
+        assert_eq!(poly.eval(-1), ratio(0, 1));
+        assert_eq!(poly.to_string(), "(1/2)x^2 + (3/2)x + 1");
+    }
+
+    #[test]
+    fn fit_constant() {
+        assert_eq!(Polynomial::fit(&[0, 0, 0]).to_string(), "0");
+        assert_eq!(Polynomial::fit(&[-4, -4]).to_string(), "-4");
+        assert_eq!(Polynomial::fit(&[10, 13, 16, 21, 30, 45]).eval(6), ratio(68, 1));
+    }
+
+    #[test]
+    fn fit_lines() {
+        let fitted: Vec<_> = fits("1 3 6 10 15 21\n0 0\n".as_bytes()).map(Result::unwrap).collect();
+
+        assert_eq!(fitted[0].line, "1 3 6 10 15 21");
+        assert_eq!(fitted[0].polynomial.degree(), 2);
+        assert_eq!((fitted[0].next.clone(), fitted[0].previous.clone()), (ratio(28, 1), ratio(0, 1)));
+        assert_eq!(fitted[1].polynomial.to_string(), "0");
+
+        assert!(matches!(fits("1 x\n".as_bytes()).next(), Some(Err(LineError::Parse(_)))));
+    }
+
+    #[test]
+    fn large_differences() {
+        // differences double each row, past the range of an i32
+        let history: Vec<i32> = (0..40).map(|i| if i % 2 == 0 { i32::MAX } else { i32::MIN }).collect();
+        let line = history.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+
+        let fit = fits(line.as_bytes()).next().unwrap().unwrap();
+        assert_eq!(fit.polynomial.eval(1), ratio(i32::MIN.into(), 1));
+        assert_eq!(fit.polynomial.degree(), 39);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_history("1 -2 3"), Ok(vec![1, -2, 3]));
+        assert_eq!(parse_history(""), Err(ParseHistoryError::Empty));
+        assert_eq!(parse_history("1  2"), Err(ParseHistoryError::InvalidValue("".into())));
+        assert_eq!(parse_history("1 x"), Err(ParseHistoryError::InvalidValue("x".into())));
+    }
+
+    #[test]
+    fn generated_answers() {
+        for seed in 1..=5 {
+            let generated = aoc::generate::generate(9, seed, 20).unwrap();
+            let input = generated.input.as_bytes();
+
+            assert_eq!(part1(input).ok().map(|t| t.to_string()), generated.part1);
+            assert_eq!(part2(input).ok().map(|t| t.to_string()), generated.part2);
+        }
+    }
+}
@@ -1,107 +1,85 @@
-use std::{env, fs, process};
+use std::{env, process};
+
+use common::{alloc::CountingAlloc, input, output::{Format, Output}, profile::Profile};
+use day9::{fits, part1, part2, Fit};
+use serde_json::json;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+enum Mode {
+    Part1,
+    Part2,
+    Fit
+}
 
 fn main() {
-    let mut args = env::args();
+    let (mut profile, args) = Profile::from_args(env::args());
+    let (output, mut args) = Output::from_args(9, args);
 
     // first arg is command name
     let cmd_name = args.next().unwrap();
 
-    let mut run_part2 = false;
+    let mut mode = Mode::Part1;
     let input_file = match args.next() {
         Some(a) if a == "-p2" => {
-            run_part2 = true;
+            mode = Mode::Part2;
+            args.next()
+        },
+        Some(a) if a == "--fit" => {
+            mode = Mode::Fit;
             args.next()
         },
         Some(a) => Some(a),
         None => None
     }.unwrap_or_else(|| {
-        println!("{cmd_name} input.txt");
+        println!("{cmd_name} [-p2|--fit] [--time] [--mem] [--format text|json] input.txt");
         process::exit(1)
     });
 
-    let file_content = fs::read_to_string(input_file)
-        .expect("input file should exist and be text file");
-
-    let total = if run_part2 {
-        part2(&file_content)
-    } else {
-        part1(&file_content)
-    };
-
-    println!("{total}");
-}
-
-fn part1(data: &str) -> i32 {
-    let mut total = 0;
-
-    for line in data.lines() {
-        let history: Vec<_> = line.split(' ')
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect();
-
-        let diffs = generate_diffs(history);
-
-        let next_value: i32 = diffs.iter()
-            .map(|s| s.last().unwrap())
-            .rev()
-            .scan(0, |acc, x| {
-                *acc = *acc + *x;
-                Some(*acc)
-            })
-            .last().unwrap();
-
-        total += next_value;
+    output.set_input(&input_file);
+    if let Mode::Part2 = mode {
+        output.set_part(2);
     }
 
-    total
-}
-
-fn part2(data: &str) -> i32 {
-    let mut total = 0;
-
-    for line in data.lines() {
-        let history: Vec<_> = line.split(' ')
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect();
-
-        let diffs = generate_diffs(history);
-
-        let first_value: i32 = diffs.iter()
-            .map(|s| s.first().unwrap())
-            .rev()
-            .scan(0, |acc, x| {
-                *acc = *x - *acc;
-                Some(*acc)
-            })
-            .last().unwrap();
-
-        total += first_value;
-    }
-
-    total
-}
-
-fn generate_diffs(history: Vec<i32>) -> Vec<Vec<i32>> {
-    let mut diffs: Vec<Vec<i32>> = vec![history];
-
-    loop {
-        if let Some(history) = diff_pairs(&diffs.last().unwrap()) {
-            diffs.push(history);
-        } else {
-            break;
+    let input = profile.phase("read", || input::try_open(&input_file))
+        .unwrap_or_else(|err| output.fail("read", err));
+
+    // histories are parsed as they are read, so solving includes reading and parsing
+    let total = match mode {
+        Mode::Part1 => profile.phase("solve", || part1(input)),
+        Mode::Part2 => profile.phase("solve", || part2(input)),
+        Mode::Fit => {
+            for fit in fits(input) {
+                let fit = fit.unwrap_or_else(|err| output.fail(err.kind(), err));
+                print_fit(&fit, output.format());
+            }
+            return;
         }
-    }
+    }.unwrap_or_else(|err| output.fail(err.kind(), err));
 
-    diffs
+    output.answer(total);
+    profile.report();
 }
 
-fn diff_pairs(history: &Vec<i32>) -> Option<Vec<i32>> {
-    if history.iter().all(|n| *n == 0) {
-        None
-    } else {
-        Some(history.iter()
-            .zip(history.iter().skip(1))
-            .map(|(a, b)| b-a)
-            .collect())
+/// Print a fit as text, or as one json object per history
+fn print_fit(fit: &Fit, format: Format) {
+    match format {
+        Format::Text => {
+            println!("{}", fit.line);
+            println!("  degree: {}", fit.polynomial.degree());
+            println!("  p(x) = {}", fit.polynomial);
+            println!("  next: {}, previous: {}", fit.next, fit.previous);
+        },
+        Format::Json => {
+            let object = json!({
+                "history": fit.line,
+                "degree": fit.polynomial.degree(),
+                "polynomial": fit.polynomial.to_string(),
+                "next": fit.next.to_string(),
+                "previous": fit.previous.to_string()
+            });
+            println!("{object}");
+        }
     }
 }
@@ -0,0 +1,9 @@
+#![no_main]
+
+use day10::PipeArea;
+use libfuzzer_sys::fuzz_target;
+
+// Any pipe grid either loads or gives a LoadError
+fuzz_target!(|data: &str| {
+    let _ = PipeArea::load(data);
+});
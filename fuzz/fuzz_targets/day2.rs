@@ -0,0 +1,14 @@
+#![no_main]
+
+use day2::Game;
+use libfuzzer_sys::fuzz_target;
+
+// Any line either parses or gives a ParseGameError, and a parsed game
+// written back out parses to the same game
+fuzz_target!(|data: &str| {
+    for line in data.lines() {
+        if let Ok(game) = line.parse::<Game>() {
+            assert_eq!(game.to_string().parse::<Game>(), Ok(game));
+        }
+    }
+});
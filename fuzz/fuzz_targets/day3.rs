@@ -0,0 +1,9 @@
+#![no_main]
+
+use day3::{Board, Options};
+use libfuzzer_sys::fuzz_target;
+
+// Any schematic either loads or gives a LoadError
+fuzz_target!(|data: &str| {
+    let _ = Board::load(data.to_string(), Options::default());
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use day5::Almanac;
+use libfuzzer_sys::fuzz_target;
+
+// Any almanac either loads or gives a LoadError, with seeds read either way
+fuzz_target!(|data: &str| {
+    let _ = Almanac::load(data.to_string(), false);
+    let _ = Almanac::load(data.to_string(), true);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use day6::{load_race_results, load_single_race_result};
+use libfuzzer_sys::fuzz_target;
+
+// Race results either load or give a LoadError, as many races or as one
+fuzz_target!(|data: &str| {
+    let _ = load_race_results(data);
+    let _ = load_single_race_result(data);
+});
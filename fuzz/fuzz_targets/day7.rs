@@ -0,0 +1,11 @@
+#![no_main]
+
+use day7::Hand;
+use libfuzzer_sys::fuzz_target;
+
+// Any line either parses to a hand or gives a ParseHandError
+fuzz_target!(|data: &str| {
+    for line in data.lines() {
+        let _ = Hand::parse(line);
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use day8::Network;
+use libfuzzer_sys::fuzz_target;
+
+// Any network either loads or gives a LoadError
+fuzz_target!(|data: &str| {
+    let _ = Network::load(data);
+});
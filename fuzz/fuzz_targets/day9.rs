@@ -0,0 +1,11 @@
+#![no_main]
+
+use day9::parse_history;
+use libfuzzer_sys::fuzz_target;
+
+// Any line either parses to a history or gives a ParseHistoryError
+fuzz_target!(|data: &str| {
+    for line in data.lines() {
+        let _ = parse_history(line);
+    }
+});
@@ -0,0 +1,19 @@
+#!/bin/bash
+# Fill corpus/<day>/ with generated inputs so fuzzing starts from valid
+# puzzles, run from the fuzz directory before `cargo fuzz run <day>`
+
+SEEDS="1 2 3"
+SIZES="1 3 10 40"
+
+cd "$(dirname "$0")"
+
+for day in 2 3 5 6 7 8 9 10; do
+    mkdir -p "corpus/day$day"
+
+    for seed in $SEEDS; do
+        for size in $SIZES; do
+            cargo run -q --release -p aoc --manifest-path ../Cargo.toml -- \
+                gen "$day" --seed "$seed" --size "$size" > "corpus/day$day/gen-$seed-$size.txt"
+        done
+    done
+done